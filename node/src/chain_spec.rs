@@ -317,6 +317,8 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		rate_review: Default::default(),
+		property: Default::default(),
 		faucets: FaucetsConfig { initial_faucets },
 	}
 }
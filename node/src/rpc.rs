@@ -84,7 +84,9 @@ where
 		+ 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
-	C::Api: profile_rpc::ProfileStorageRuntimeApi<Block, AccountId, Hash>,
+	C::Api: listings_rpc::ListingsRuntimeApi<Block, AccountId, Hash, BlockNumber>,
+	C::Api: profile_rpc::ProfileStorageRuntimeApi<Block, AccountId, Hash, BlockNumber>,
+	C::Api: rate_review_rpc::RateReviewRuntimeApi<Block, AccountId, Hash, BlockNumber, Balance>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
@@ -92,8 +94,10 @@ where
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
+	use listings_rpc::{Listings, ListingsApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use profile_rpc::{ProfileStorage, ProfileStorageApi};
+	use rate_review_rpc::{RateReview, RateReviewApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
@@ -113,6 +117,10 @@ where
 
 	io.extend_with(ProfileStorageApi::to_delegate(ProfileStorage::new(client.clone())));
 
+	io.extend_with(RateReviewApi::to_delegate(RateReview::new(client.clone())));
+
+	io.extend_with(ListingsApi::to_delegate(Listings::new(client.clone())));
+
 	io.extend_with(sc_consensus_babe_rpc::BabeApi::to_delegate(BabeRpcHandler::new(
 		client.clone(),
 		shared_epoch_changes.clone(),
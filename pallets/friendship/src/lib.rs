@@ -13,15 +13,36 @@ mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::{traits::Saturating, Percent},
+	};
 	use frame_system::pallet_prelude::*;
 
+	/// Tells whose connection requests count towards the response rate of the accounts they are
+	/// sent to, so that fresh accounts cannot drag it down by sending requests nobody answers.
+	pub trait RequesterStanding<AccountId> {
+		fn counts(requester: &AccountId) -> bool;
+	}
+
+	impl<AccountId> RequesterStanding<AccountId> for () {
+		fn counts(_requester: &AccountId) -> bool {
+			true
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		#[pallet::constant]
 		type MaxConnectionRequests: Get<u32>;
+
+		/// How many of the latest response times are kept to derive the median from.
+		#[pallet::constant]
+		type MaxResponseSamples: Get<u32>;
+
+		type RequesterStanding: RequesterStanding<Self::AccountId>;
 	}
 
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -77,6 +98,40 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// (sender, destination) => block the pending connection request was sent at
+	#[pallet::storage]
+	#[pallet::getter(fn requested_at)]
+	pub type RequestedAt<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::AccountId), T::BlockNumber, OptionQuery>;
+
+	/// (sender, destination) => whether the pending request is left out of the destination's
+	/// response rate
+	#[pallet::storage]
+	#[pallet::getter(fn uncounted_requests)]
+	pub type UncountedRequests<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::AccountId), bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn requests_received)]
+	pub type RequestsReceived<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn requests_answered)]
+	pub type RequestsAnswered<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// account => blocks it took to answer its latest connection requests, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn response_times)]
+	pub type ResponseTimes<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::BlockNumber, T::MaxResponseSamples>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -153,7 +208,7 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
 		pub fn connect(
 			origin: OriginFor<T>,
 			destination: T::AccountId,
@@ -162,9 +217,19 @@ pub mod pallet {
 
 			let key = (&sender, &destination);
 
-			ensure!(<Connected<T>>::contains_key(key), Error::<T>::AlreadyConnected);
+			ensure!(!<Connected<T>>::contains_key(key), Error::<T>::AlreadyConnected);
 
 			<Connected<T>>::insert(key, ConnectionStatus::Pending);
+			<RequestedAt<T>>::insert(key, <frame_system::Pallet<T>>::block_number());
+			if T::RequesterStanding::counts(&sender) {
+				let new_received_cnt = Self::requests_received(&destination)
+					.checked_add(1)
+					.ok_or(Error::<T>::StorageOverflow)?;
+
+				<RequestsReceived<T>>::insert(&destination, new_received_cnt);
+			} else {
+				<UncountedRequests<T>>::insert(key, true);
+			}
 			Self::deposit_event(Event::<T>::Connect(
 				sender,
 				destination,
@@ -174,7 +239,7 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7, 8))]
 		pub fn connect_response(
 			origin: OriginFor<T>,
 			destination: T::AccountId,
@@ -187,6 +252,8 @@ pub mod pallet {
 
 			ensure!(connection == ConnectionStatus::Pending, Error::<T>::ConnectionIsNotPending);
 
+			Self::note_response(&sender, &destination)?;
+
 			if accept {
 				let key_reversed = (&sender, &destination);
 				let new_sender_connection_cnt = Self::connected_cnt(&sender)
@@ -246,4 +313,62 @@ pub mod pallet {
 			Ok(().into())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Median number of blocks `account` took to answer its latest connection requests.
+		pub fn median_response_time(account: &T::AccountId) -> Option<T::BlockNumber> {
+			let mut times = Self::response_times(account).into_inner();
+			times.sort();
+
+			let mid = times.len() / 2;
+			match times.len() {
+				0 => None,
+				len if len % 2 == 1 => Some(times[mid]),
+				_ => Some(
+					times[mid - 1]
+						.saturating_add(times[mid].saturating_sub(times[mid - 1]) / 2u32.into()),
+				),
+			}
+		}
+
+		/// Share of the counted connection requests `account` received that it answered.
+		pub fn response_rate(account: &T::AccountId) -> Option<Percent> {
+			let received = Self::requests_received(account);
+
+			if received == 0 {
+				return None;
+			}
+
+			Some(Percent::from_rational(Self::requests_answered(account), received))
+		}
+
+		fn note_response(responder: &T::AccountId, requester: &T::AccountId) -> DispatchResult {
+			let requested_at = match <RequestedAt<T>>::take((requester, responder)) {
+				Some(x) => x,
+				// Requests sent before response times were tracked.
+				None => return Ok(()),
+			};
+
+			// Requests that were not counted as received are not counted as answered either, and
+			// their response times are left out so that accounts cannot speed up their own median.
+			if <UncountedRequests<T>>::take((requester, responder)) {
+				return Ok(());
+			}
+
+			let elapsed = <frame_system::Pallet<T>>::block_number().saturating_sub(requested_at);
+			let new_answered_cnt = Self::requests_answered(responder)
+				.checked_add(1)
+				.ok_or(Error::<T>::StorageOverflow)?;
+
+			<ResponseTimes<T>>::mutate(responder, |times| {
+				if times.try_push(elapsed).is_err() && !times.is_empty() {
+					times.remove(0);
+					let _ = times.try_push(elapsed);
+				}
+			});
+			<RequestsAnswered<T>>::insert(responder, new_answered_cnt);
+
+			Ok(())
+		}
+	}
 }
@@ -1,5 +1,5 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate as pallet_friendship;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
@@ -18,7 +18,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Friendship: pallet_friendship::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -49,11 +49,26 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+/// Accounts from 100 on are fresh, their requests do not count towards response rates.
+pub struct MockStanding;
+
+impl pallet_friendship::RequesterStanding<u64> for MockStanding {
+	fn counts(requester: &u64) -> bool {
+		*requester < 100
+	}
+}
+
+impl pallet_friendship::Config for Test {
 	type Event = Event;
+	type MaxConnectionRequests = ConstU32<10>;
+	type MaxResponseSamples = ConstU32<5>;
+	type RequesterStanding = MockStanding;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut ext: sp_io::TestExternalities =
+		system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
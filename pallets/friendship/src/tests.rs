@@ -1,20 +1,119 @@
-use crate::{mock::*, Error};
+use crate::{mock::*, ConnectionStatus, Error};
 use frame_support::{assert_noop, assert_ok};
+use sp_runtime::Percent;
+
+const PRO: u64 = 1;
+
+fn request(from: u64) {
+	assert_ok!(Friendship::connect(Origin::signed(from), PRO));
+}
+
+fn answer(from: u64, accept: bool) {
+	assert_ok!(Friendship::connect_response(Origin::signed(PRO), from, accept));
+}
+
+/// Answers a request sent by `from` `blocks` blocks after it was sent.
+fn answer_after(from: u64, blocks: u64) {
+	let now = System::block_number();
+
+	request(from);
+	System::set_block_number(now + blocks);
+	answer(from, true);
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn connect_response_connects_both_ways() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		request(2);
+		assert_eq!(Friendship::connected((2, PRO)), ConnectionStatus::Pending);
+		assert_noop!(Friendship::connect(Origin::signed(2), PRO), Error::<Test>::AlreadyConnected);
+		assert_noop!(
+			Friendship::connect_response(Origin::signed(PRO), 3, true),
+			Error::<Test>::ConnectionIsNotPending
+		);
+
+		answer(2, true);
+		assert_eq!(Friendship::connected((2, PRO)), ConnectionStatus::Connected);
+		assert_eq!(Friendship::connected((PRO, 2)), ConnectionStatus::Connected);
+		assert_eq!((Friendship::connected_cnt(PRO), Friendship::connected_cnt(2)), (1, 1));
+
+		request(3);
+		answer(3, false);
+		assert_eq!(Friendship::connected((3, PRO)), ConnectionStatus::Rejected);
+		assert_eq!(Friendship::connected_cnt(PRO), 1);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn median_response_time_of_odd_and_even_samples() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_eq!(Friendship::median_response_time(&PRO), None);
+
+		answer_after(2, 10);
+		assert_eq!(Friendship::median_response_time(&PRO), Some(10));
+
+		answer_after(3, 1);
+		answer_after(4, 4);
+		// 1, 4, 10
+		assert_eq!(Friendship::median_response_time(&PRO), Some(4));
+
+		answer_after(5, 9);
+		// 1, 4, 9, 10: halfway between 4 and 9, rounded down
+		assert_eq!(Friendship::median_response_time(&PRO), Some(6));
+	});
+}
+
+#[test]
+fn median_response_time_only_keeps_the_latest_samples() {
+	new_test_ext().execute_with(|| {
+		for (from, blocks) in [(2, 100), (3, 100), (4, 100), (5, 1), (6, 1)] {
+			answer_after(from, blocks);
+		}
+		assert_eq!(Friendship::median_response_time(&PRO), Some(100));
+
+		answer_after(7, 1);
+		assert_eq!(Friendship::response_times(PRO).into_inner(), vec![100, 100, 1, 1, 1]);
+		assert_eq!(Friendship::median_response_time(&PRO), Some(1));
+	});
+}
+
+#[test]
+fn response_rate_counts_accepted_and_rejected_answers() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Friendship::response_rate(&PRO), None);
+
+		for from in 2..6 {
+			request(from);
+		}
+		assert_eq!(Friendship::response_rate(&PRO), Some(Percent::from_percent(0)));
+
+		answer(2, true);
+		answer(3, false);
+		answer(4, true);
+		assert_eq!(Friendship::requests_received(PRO), 4);
+		assert_eq!(Friendship::requests_answered(PRO), 3);
+		assert_eq!(Friendship::response_rate(&PRO), Some(Percent::from_percent(75)));
+	});
+}
+
+#[test]
+fn requests_from_fresh_accounts_do_not_count() {
+	new_test_ext().execute_with(|| {
+		answer_after(2, 4);
+
+		for from in 100..110 {
+			request(from);
+		}
+		assert_eq!(Friendship::requests_received(PRO), 1);
+		assert_eq!(Friendship::response_rate(&PRO), Some(Percent::from_percent(100)));
+		assert!(Friendship::uncounted_requests((100, PRO)));
+
+		// Answering them does not count either, quickly or not.
+		answer(100, true);
+		assert_eq!(Friendship::connected((100, PRO)), ConnectionStatus::Connected);
+		assert!(!Friendship::uncounted_requests((100, PRO)));
+		assert_eq!(Friendship::requests_answered(PRO), 1);
+		assert_eq!(Friendship::response_times(PRO).into_inner(), vec![4]);
+		assert_eq!(Friendship::response_rate(&PRO), Some(Percent::from_percent(100)));
 	});
 }
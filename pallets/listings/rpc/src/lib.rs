@@ -0,0 +1,67 @@
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use listings_runtime_api::ListingsApi as ListingsRuntimeApi;
+use pallet_listings::{rpc::ListingView, ListingStatus};
+use rp_profile::Area;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::scale_info::TypeInfo;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc]
+pub trait ListingsApi<BlockHash, AccountId, Hash, BlockNumber> {
+	#[rpc(name = "listings_getListings")]
+	fn get_listings(
+		&self,
+		at: Option<BlockHash>,
+		area: Area,
+		status: Option<ListingStatus>,
+		offset: u64,
+		limit: u16,
+	) -> Result<Vec<ListingView<AccountId, Hash, BlockNumber>>>;
+}
+
+pub struct Listings<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Listings<C, P> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Hash, BlockNumber>
+	ListingsApi<<Block as BlockT>::Hash, AccountId, Hash, BlockNumber> for Listings<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ListingsRuntimeApi<Block, AccountId, Hash, BlockNumber>,
+	AccountId: Encode + Decode + Clone + PartialEq + TypeInfo,
+	Hash: Encode + Decode + Clone + PartialEq + TypeInfo,
+	BlockNumber: Encode + Decode + Clone + PartialEq + TypeInfo,
+{
+	fn get_listings(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		area: Area,
+		status: Option<ListingStatus>,
+		offset: u64,
+		limit: u16,
+	) -> Result<Vec<ListingView<AccountId, Hash, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_listings(&at, area, status, offset, limit);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2401), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
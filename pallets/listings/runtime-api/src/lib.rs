@@ -0,0 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Decode, Encode};
+use pallet_listings::{rpc::ListingView, ListingStatus};
+use rp_profile::Area;
+use scale_info::TypeInfo;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait ListingsApi<AccountId, Hash, BlockNumber> where
+		AccountId: Encode + Decode + Clone + PartialEq + TypeInfo,
+		Hash: Encode + Decode + Clone + PartialEq + TypeInfo,
+		BlockNumber: Encode + Decode + Clone + PartialEq + TypeInfo,
+	{
+		fn get_listings(area: Area, status: Option<ListingStatus>, offset: u64, limit: u16) -> Vec<ListingView<AccountId, Hash, BlockNumber>>;
+	}
+}
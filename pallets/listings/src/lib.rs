@@ -0,0 +1,316 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+pub mod rpc;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use rp_profile::Area;
+	#[cfg(feature = "std")]
+	use serde::{Deserialize, Serialize};
+
+	/// ISO 4217 code of the currency a price is given in, e.g. `b"USD"`.
+	pub type CurrencyCode = [u8; 3];
+
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum ListingStatus {
+		Draft,
+		ForSale,
+		ForRent,
+		UnderOffer,
+		Sold,
+		Withdrawn,
+	}
+
+	impl ListingStatus {
+		/// Whether the property is on the market.
+		pub fn is_active(&self) -> bool {
+			matches!(self, Self::ForSale | Self::ForRent | Self::UnderOffer)
+		}
+
+		/// Whether the listing is over and the property can be listed again.
+		pub fn is_closed(&self) -> bool {
+			matches!(self, Self::Sold | Self::Withdrawn)
+		}
+
+		/// Whether a listing can move from this status to `next`. Sold and withdrawn listings
+		/// are final.
+		pub fn can_become(&self, next: Self) -> bool {
+			use ListingStatus::*;
+
+			match self {
+				Draft => matches!(next, ForSale | ForRent | Withdrawn),
+				ForSale | ForRent => {
+					matches!(next, Draft | ForSale | ForRent | UnderOffer | Withdrawn)
+				},
+				UnderOffer => matches!(next, ForSale | ForRent | Sold | Withdrawn),
+				Sold | Withdrawn => false,
+			}
+		}
+	}
+
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct Listing<AccountId, BlockNumber> {
+		/// Owner of the property when it was listed. The agent can only act for them.
+		pub owner: AccountId,
+		pub agent: AccountId,
+		pub status: ListingStatus,
+		/// In the smallest unit of `currency`, e.g. cents.
+		pub price: u128,
+		pub currency: CurrencyCode,
+		pub updated: BlockNumber,
+	}
+
+	/// Tells which properties exist, who owns them and where they are.
+	pub trait PropertyRegistry<PropertyId, AccountId> {
+		fn is_owner(property_id: &PropertyId, who: &AccountId) -> bool;
+
+		fn area(property_id: &PropertyId) -> Option<Area>;
+	}
+
+	impl<PropertyId, AccountId> PropertyRegistry<PropertyId, AccountId> for () {
+		fn is_owner(_property_id: &PropertyId, _who: &AccountId) -> bool {
+			false
+		}
+
+		fn area(_property_id: &PropertyId) -> Option<Area> {
+			None
+		}
+	}
+
+	/// Tells who may act as the agent of a listing.
+	pub trait ListingAgents<AccountId> {
+		fn is_agent(who: &AccountId) -> bool;
+	}
+
+	impl<AccountId> ListingAgents<AccountId> for () {
+		fn is_agent(_who: &AccountId) -> bool {
+			false
+		}
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Decides who may list a property.
+		type PropertyRegistry: PropertyRegistry<Self::Hash, Self::AccountId>;
+
+		/// Decides who may be named listing agent, e.g. real estate agents and brokers.
+		type ListingAgents: ListingAgents<Self::AccountId>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// property => listing
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	pub type Listings<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::Hash,
+		Listing<T::AccountId, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// [property, agent]
+		ListingCreated(T::Hash, T::AccountId),
+		/// [property, old_status, new_status]
+		ListingStatusChanged(T::Hash, ListingStatus, ListingStatus),
+		/// [property, price, currency]
+		ListingPriceChanged(T::Hash, u128, CurrencyCode),
+		/// [property, agent]
+		ListingAgentChanged(T::Hash, T::AccountId),
+		/// [property]
+		ListingRemoved(T::Hash),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		NotPropertyOwner,
+		NotListingAgent,
+		NotOwnerOrAgent,
+		ListingAlreadyCreated,
+		ListingNotFound,
+		StatusUnchanged,
+		InvalidStatusTransition,
+		ListingOwnerChanged,
+		ListingClosed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Lists the sender's property as a draft. A sold or withdrawn listing is replaced, and so
+		/// is one created by a previous owner.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1))]
+		pub fn create_listing(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			agent: T::AccountId,
+			price: u128,
+			currency: CurrencyCode,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				T::PropertyRegistry::is_owner(&property_id, &sender),
+				Error::<T>::NotPropertyOwner
+			);
+			ensure!(T::ListingAgents::is_agent(&agent), Error::<T>::NotListingAgent);
+			ensure!(
+				Self::listings(&property_id).map_or(true, |x| x.status.is_closed()
+					|| !T::PropertyRegistry::is_owner(&property_id, &x.owner)),
+				Error::<T>::ListingAlreadyCreated
+			);
+
+			Listings::<T>::insert(
+				&property_id,
+				Listing {
+					owner: sender,
+					agent: agent.clone(),
+					status: ListingStatus::Draft,
+					price,
+					currency,
+					updated: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+			Self::deposit_event(Event::<T>::ListingCreated(property_id, agent));
+
+			Ok(())
+		}
+
+		/// The sender is the owner of the property or the agent of the listing, see
+		/// `ListingStatus::can_become` for the allowed changes.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1))]
+		pub fn set_listing_status(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			status: ListingStatus,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut listing = Self::listings(&property_id).ok_or(Error::<T>::ListingNotFound)?;
+
+			Self::ensure_owner_or_agent(&property_id, &listing, &sender)?;
+			ensure!(listing.status != status, Error::<T>::StatusUnchanged);
+			ensure!(listing.status.can_become(status), Error::<T>::InvalidStatusTransition);
+
+			let old_status = listing.status;
+
+			listing.status = status;
+			listing.updated = <frame_system::Pallet<T>>::block_number();
+
+			Listings::<T>::insert(&property_id, listing);
+			Self::deposit_event(Event::<T>::ListingStatusChanged(property_id, old_status, status));
+
+			Ok(())
+		}
+
+		/// The sender is the owner of the property or the agent of the listing, which is still
+		/// open.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1))]
+		pub fn set_listing_price(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			price: u128,
+			currency: CurrencyCode,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut listing = Self::listings(&property_id).ok_or(Error::<T>::ListingNotFound)?;
+
+			Self::ensure_owner_or_agent(&property_id, &listing, &sender)?;
+			ensure!(!listing.status.is_closed(), Error::<T>::ListingClosed);
+
+			listing.price = price;
+			listing.currency = currency;
+			listing.updated = <frame_system::Pallet<T>>::block_number();
+
+			Listings::<T>::insert(&property_id, listing);
+			Self::deposit_event(Event::<T>::ListingPriceChanged(property_id, price, currency));
+
+			Ok(())
+		}
+
+		/// Sold and withdrawn listings keep their agent, a new listing is created instead.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn set_listing_agent(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			agent: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				T::PropertyRegistry::is_owner(&property_id, &sender),
+				Error::<T>::NotPropertyOwner
+			);
+			ensure!(T::ListingAgents::is_agent(&agent), Error::<T>::NotListingAgent);
+
+			let mut listing = Self::listings(&property_id).ok_or(Error::<T>::ListingNotFound)?;
+
+			ensure!(!listing.status.is_closed(), Error::<T>::ListingClosed);
+
+			listing.owner = sender;
+			listing.agent = agent.clone();
+			listing.updated = <frame_system::Pallet<T>>::block_number();
+
+			Listings::<T>::insert(&property_id, listing);
+			Self::deposit_event(Event::<T>::ListingAgentChanged(property_id, agent));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn remove_listing(origin: OriginFor<T>, property_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				T::PropertyRegistry::is_owner(&property_id, &sender),
+				Error::<T>::NotPropertyOwner
+			);
+			ensure!(Listings::<T>::contains_key(&property_id), Error::<T>::ListingNotFound);
+
+			Listings::<T>::remove(&property_id);
+			Self::deposit_event(Event::<T>::ListingRemoved(property_id));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The agent has to still be eligible, and to act for the current owner of the property.
+		fn ensure_owner_or_agent(
+			property_id: &T::Hash,
+			listing: &Listing<T::AccountId, T::BlockNumber>,
+			who: &T::AccountId,
+		) -> DispatchResult {
+			if T::PropertyRegistry::is_owner(property_id, who) {
+				return Ok(());
+			}
+
+			ensure!(&listing.agent == who, Error::<T>::NotOwnerOrAgent);
+			ensure!(T::ListingAgents::is_agent(who), Error::<T>::NotListingAgent);
+			ensure!(
+				T::PropertyRegistry::is_owner(property_id, &listing.owner),
+				Error::<T>::ListingOwnerChanged
+			);
+			Ok(())
+		}
+	}
+}
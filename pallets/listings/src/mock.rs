@@ -0,0 +1,112 @@
+use crate as pallet_listings;
+use frame_support::traits::{ConstU16, ConstU64};
+use frame_system as system;
+use rp_profile::Area;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use std::{
+	cell::RefCell,
+	collections::{BTreeMap, BTreeSet},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Listings: pallet_listings::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+thread_local! {
+	static PROPERTIES: RefCell<BTreeMap<H256, (u64, Area)>> = RefCell::new(BTreeMap::new());
+	static AGENTS: RefCell<BTreeSet<u64>> = RefCell::new(BTreeSet::new());
+}
+
+/// Properties and agents kept in memory, set up by the tests.
+pub struct MockRegistry;
+
+impl MockRegistry {
+	pub fn set_property(property_id: H256, owner: u64, area: Area) {
+		PROPERTIES.with(|x| x.borrow_mut().insert(property_id, (owner, area)));
+	}
+
+	pub fn set_agent(who: u64, is_agent: bool) {
+		AGENTS.with(|x| {
+			if is_agent {
+				x.borrow_mut().insert(who)
+			} else {
+				x.borrow_mut().remove(&who)
+			}
+		});
+	}
+}
+
+impl pallet_listings::PropertyRegistry<H256, u64> for MockRegistry {
+	fn is_owner(property_id: &H256, who: &u64) -> bool {
+		PROPERTIES.with(|x| x.borrow().get(property_id).map_or(false, |(owner, _)| owner == who))
+	}
+
+	fn area(property_id: &H256) -> Option<Area> {
+		PROPERTIES.with(|x| x.borrow().get(property_id).map(|(_, area)| *area))
+	}
+}
+
+impl pallet_listings::ListingAgents<u64> for MockRegistry {
+	fn is_agent(who: &u64) -> bool {
+		AGENTS.with(|x| x.borrow().contains(who))
+	}
+}
+
+impl pallet_listings::Config for Test {
+	type Event = Event;
+	type PropertyRegistry = MockRegistry;
+	type ListingAgents = MockRegistry;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	PROPERTIES.with(|x| x.borrow_mut().clear());
+	AGENTS.with(|x| x.borrow_mut().clear());
+
+	let mut ext: sp_io::TestExternalities =
+		system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,62 @@
+use crate::{Config, CurrencyCode, Listing, ListingStatus, Listings, Pallet, PropertyRegistry};
+use codec::{Decode, Encode};
+use rp_profile::Area;
+use scale_info::prelude::vec::Vec;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Encode, Decode, Clone, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct ListingView<AccountId, Hash, BlockNumber> {
+	pub property_id: Hash,
+	pub area: Area,
+	pub agent: AccountId,
+	pub status: ListingStatus,
+	/// In the smallest unit of `currency`, e.g. cents.
+	pub price: u128,
+	pub currency: CurrencyCode,
+	pub updated: BlockNumber,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Listings in `area` with the given status, or every active listing in `area` when no
+	/// status is given. Listings left behind by a previous owner are skipped. Most recently
+	/// updated first.
+	pub fn get_listings(
+		area: Area,
+		status: Option<ListingStatus>,
+		offset: u64,
+		limit: u16,
+	) -> Vec<ListingView<T::AccountId, T::Hash, T::BlockNumber>> {
+		let mut listings = Listings::<T>::iter()
+			.filter(|(_, listing)| {
+				status.map_or(listing.status.is_active(), |x| listing.status == x)
+			})
+			.filter(|(property_id, listing)| {
+				T::PropertyRegistry::is_owner(property_id, &listing.owner)
+			})
+			.filter(|(property_id, _)| T::PropertyRegistry::area(property_id) == Some(area))
+			.map(|(property_id, listing)| Self::listing_view(property_id, area, listing))
+			.collect::<Vec<_>>();
+
+		listings.sort_by(|a, b| b.updated.cmp(&a.updated));
+		listings.into_iter().skip(offset as usize).take(limit as usize).collect()
+	}
+
+	fn listing_view(
+		property_id: T::Hash,
+		area: Area,
+		listing: Listing<T::AccountId, T::BlockNumber>,
+	) -> ListingView<T::AccountId, T::Hash, T::BlockNumber> {
+		ListingView {
+			property_id,
+			area,
+			agent: listing.agent,
+			status: listing.status,
+			price: listing.price,
+			currency: listing.currency,
+			updated: listing.updated,
+		}
+	}
+}
@@ -0,0 +1,215 @@
+use crate::{mock::*, Error, ListingStatus};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+use ListingStatus::*;
+
+const OWNER: u64 = 1;
+const AGENT: u64 = 10;
+
+fn listed(n: u8, area: u32) -> H256 {
+	let property_id = H256::repeat_byte(n);
+
+	MockRegistry::set_property(property_id, OWNER, area);
+	MockRegistry::set_agent(AGENT, true);
+	assert_ok!(Listings::create_listing(Origin::signed(OWNER), property_id, AGENT, 100, *b"USD"));
+
+	property_id
+}
+
+fn set_status(property_id: H256, status: ListingStatus) {
+	assert_ok!(Listings::set_listing_status(Origin::signed(OWNER), property_id, status));
+}
+
+#[test]
+fn can_become_allows_only_the_listing_lifecycle() {
+	let all = [Draft, ForSale, ForRent, UnderOffer, Sold, Withdrawn];
+	let allowed = |from: ListingStatus| -> Vec<ListingStatus> {
+		all.iter().copied().filter(|x| from.can_become(*x)).collect()
+	};
+
+	assert_eq!(allowed(Draft), vec![ForSale, ForRent, Withdrawn]);
+	assert_eq!(allowed(ForSale), vec![Draft, ForSale, ForRent, UnderOffer, Withdrawn]);
+	assert_eq!(allowed(ForRent), vec![Draft, ForSale, ForRent, UnderOffer, Withdrawn]);
+	assert_eq!(allowed(UnderOffer), vec![ForSale, ForRent, Sold, Withdrawn]);
+	assert_eq!(allowed(Sold), vec![]);
+	assert_eq!(allowed(Withdrawn), vec![]);
+}
+
+#[test]
+fn set_listing_status_enforces_the_transitions() {
+	new_test_ext().execute_with(|| {
+		let property_id = listed(1, 7);
+
+		assert_noop!(
+			Listings::set_listing_status(Origin::signed(OWNER), property_id, Draft),
+			Error::<Test>::StatusUnchanged
+		);
+		assert_noop!(
+			Listings::set_listing_status(Origin::signed(OWNER), property_id, Sold),
+			Error::<Test>::InvalidStatusTransition
+		);
+
+		set_status(property_id, ForSale);
+		assert_ok!(Listings::set_listing_status(Origin::signed(AGENT), property_id, UnderOffer));
+		set_status(property_id, Sold);
+
+		assert_noop!(
+			Listings::set_listing_status(Origin::signed(OWNER), property_id, ForSale),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_eq!(Listings::listings(property_id).unwrap().status, Sold);
+	});
+}
+
+#[test]
+fn closed_listings_keep_their_price_and_agent() {
+	new_test_ext().execute_with(|| {
+		let property_id = listed(1, 7);
+		MockRegistry::set_agent(11, true);
+		set_status(property_id, Withdrawn);
+
+		assert_noop!(
+			Listings::set_listing_price(Origin::signed(OWNER), property_id, 90, *b"EUR"),
+			Error::<Test>::ListingClosed
+		);
+		assert_noop!(
+			Listings::set_listing_price(Origin::signed(AGENT), property_id, 90, *b"EUR"),
+			Error::<Test>::ListingClosed
+		);
+		assert_noop!(
+			Listings::set_listing_agent(Origin::signed(OWNER), property_id, 11),
+			Error::<Test>::ListingClosed
+		);
+
+		// The property can be listed again instead.
+		assert_ok!(Listings::create_listing(Origin::signed(OWNER), property_id, 11, 90, *b"EUR"));
+		assert_eq!(Listings::listings(property_id).unwrap().status, Draft);
+	});
+}
+
+#[test]
+fn agent_can_only_act_while_eligible_and_named() {
+	new_test_ext().execute_with(|| {
+		let property_id = listed(1, 7);
+		MockRegistry::set_agent(11, true);
+
+		assert_noop!(
+			Listings::set_listing_price(Origin::signed(11), property_id, 90, *b"USD"),
+			Error::<Test>::NotOwnerOrAgent
+		);
+		assert_ok!(Listings::set_listing_price(Origin::signed(AGENT), property_id, 90, *b"USD"));
+		assert_eq!(Listings::listings(property_id).unwrap().price, 90);
+
+		assert_ok!(Listings::set_listing_agent(Origin::signed(OWNER), property_id, 11));
+
+		assert_noop!(
+			Listings::set_listing_price(Origin::signed(AGENT), property_id, 80, *b"USD"),
+			Error::<Test>::NotOwnerOrAgent
+		);
+
+		MockRegistry::set_agent(11, false);
+
+		assert_noop!(
+			Listings::set_listing_status(Origin::signed(11), property_id, ForSale),
+			Error::<Test>::NotListingAgent
+		);
+		assert_noop!(
+			Listings::set_listing_agent(Origin::signed(OWNER), property_id, 11),
+			Error::<Test>::NotListingAgent
+		);
+	});
+}
+
+#[test]
+fn agent_cannot_act_after_the_property_changed_hands() {
+	new_test_ext().execute_with(|| {
+		let property_id = listed(1, 7);
+
+		MockRegistry::set_property(property_id, 2, 7);
+
+		assert_noop!(
+			Listings::set_listing_status(Origin::signed(AGENT), property_id, ForSale),
+			Error::<Test>::ListingOwnerChanged
+		);
+		assert_noop!(
+			Listings::set_listing_price(Origin::signed(AGENT), property_id, 90, *b"USD"),
+			Error::<Test>::ListingOwnerChanged
+		);
+		assert_noop!(
+			Listings::set_listing_status(Origin::signed(OWNER), property_id, ForSale),
+			Error::<Test>::NotOwnerOrAgent
+		);
+
+		// The new owner takes the listing over by naming an agent.
+		assert_ok!(Listings::set_listing_agent(Origin::signed(2), property_id, AGENT));
+		assert_eq!(Listings::listings(property_id).unwrap().owner, 2);
+		assert_ok!(Listings::set_listing_status(Origin::signed(AGENT), property_id, ForSale));
+	});
+}
+
+#[test]
+fn create_listing_replaces_only_closed_or_stale_listings() {
+	new_test_ext().execute_with(|| {
+		let property_id = listed(1, 7);
+
+		assert_noop!(
+			Listings::create_listing(Origin::signed(OWNER), property_id, AGENT, 100, *b"USD"),
+			Error::<Test>::ListingAlreadyCreated
+		);
+		assert_noop!(
+			Listings::create_listing(Origin::signed(2), property_id, AGENT, 100, *b"USD"),
+			Error::<Test>::NotPropertyOwner
+		);
+		assert_noop!(
+			Listings::create_listing(Origin::signed(OWNER), H256::repeat_byte(1), 11, 100, *b"USD"),
+			Error::<Test>::NotListingAgent
+		);
+
+		MockRegistry::set_property(property_id, 2, 7);
+
+		assert_ok!(Listings::create_listing(Origin::signed(2), property_id, AGENT, 120, *b"USD"));
+		assert_eq!(Listings::listings(property_id).unwrap().owner, 2);
+	});
+}
+
+#[test]
+fn get_listings_filters_by_area_status_and_owner() {
+	new_test_ext().execute_with(|| {
+		let for_sale = listed(1, 7);
+		set_status(for_sale, ForSale);
+
+		System::set_block_number(2);
+		let for_rent = listed(2, 7);
+		set_status(for_rent, ForRent);
+
+		System::set_block_number(3);
+		let draft = listed(3, 7);
+
+		let elsewhere = listed(4, 8);
+		set_status(elsewhere, ForSale);
+
+		let stale = listed(5, 7);
+		set_status(stale, ForSale);
+		MockRegistry::set_property(stale, 2, 7);
+
+		let ids = |status: Option<ListingStatus>, offset: u64, limit: u16| -> Vec<H256> {
+			Listings::get_listings(7, status, offset, limit)
+				.into_iter()
+				.map(|x| x.property_id)
+				.collect()
+		};
+
+		// Active listings, most recently updated first.
+		assert_eq!(ids(None, 0, 10), vec![for_rent, for_sale]);
+		assert_eq!(ids(Some(Draft), 0, 10), vec![draft]);
+		assert_eq!(ids(Some(ForSale), 0, 10), vec![for_sale]);
+		assert_eq!(ids(None, 1, 10), vec![for_sale]);
+		assert_eq!(ids(None, 0, 1), vec![for_rent]);
+
+		let view = &Listings::get_listings(8, None, 0, 10)[0];
+		assert_eq!(
+			(view.property_id, view.area, view.agent, view.status, view.price, view.updated),
+			(elsewhere, 8, AGENT, ForSale, 100, 3)
+		);
+	});
+}
@@ -3,15 +3,15 @@ use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 use pallet_profile::rpc::ProProfile;
 pub use profile_runtime_api::ProfileApi as ProfileStorageRuntimeApi;
-use rp_profile::{Area, Profession};
-use sp_api::ProvideRuntimeApi;
+use rp_profile::{Area, Media, PrivateField, Profession};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::scale_info::TypeInfo;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::sync::Arc;
 
 #[rpc]
-pub trait ProfileStorageApi<BlockHash, AccountId, Hash> {
+pub trait ProfileStorageApi<BlockHash, AccountId, Hash, BlockNumber> {
 	#[rpc(name = "profile_isUsernameExist")]
 	fn is_username_exist(&self, at: Option<BlockHash>, username: Hash) -> Result<bool>;
 	#[rpc(name = "profile_getProProfiles")]
@@ -22,7 +22,19 @@ pub trait ProfileStorageApi<BlockHash, AccountId, Hash> {
 		area: Area,
 		offset: u64,
 		limit: u16,
-	) -> Result<Vec<ProProfile<AccountId>>>;
+		include_unavailable: Option<bool>,
+	) -> Result<Vec<ProProfile<AccountId, BlockNumber>>>;
+	#[rpc(name = "profile_getProfileMedia")]
+	fn get_profile_media(&self, at: Option<BlockHash>, account_id: AccountId)
+		-> Result<Vec<Media>>;
+	#[rpc(name = "profile_getPrivateField")]
+	fn get_private_field(
+		&self,
+		at: Option<BlockHash>,
+		owner: AccountId,
+		field: PrivateField,
+		viewer: AccountId,
+	) -> Result<Option<(Vec<u8>, Vec<u8>)>>;
 }
 
 pub struct ProfileStorage<C, P> {
@@ -36,14 +48,16 @@ impl<C, P> ProfileStorage<C, P> {
 	}
 }
 
-impl<C, Block, AccountId, Hash> ProfileStorageApi<<Block as BlockT>::Hash, AccountId, Hash>
+impl<C, Block, AccountId, Hash, BlockNumber>
+	ProfileStorageApi<<Block as BlockT>::Hash, AccountId, Hash, BlockNumber>
 	for ProfileStorage<C, Block>
 where
 	Block: BlockT,
 	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: ProfileStorageRuntimeApi<Block, AccountId, Hash>,
+	C::Api: ProfileStorageRuntimeApi<Block, AccountId, Hash, BlockNumber>,
 	AccountId: Encode + Decode + Clone + PartialEq + TypeInfo,
 	Hash: Encode + Decode + Default + Clone + PartialEq + TypeInfo,
+	BlockNumber: Encode + Decode + Clone + PartialEq + TypeInfo,
 {
 	fn is_username_exist(
 		&self,
@@ -63,6 +77,8 @@ where
 		})
 	}
 
+	// `get_pros_before_version_2` is deprecated, but still served by older runtimes.
+	#[allow(deprecated)]
 	fn get_pros(
 		&self,
 		at: Option<<Block as BlockT>::Hash>,
@@ -70,17 +86,71 @@ where
 		area: Area,
 		offset: u64,
 		limit: u16,
-	) -> Result<Vec<ProProfile<AccountId>>> {
+		include_unavailable: Option<bool>,
+	) -> Result<Vec<ProProfile<AccountId, BlockNumber>>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash));
 
-		let runtime_api_result = api.get_pros(&at, profession, area, offset, limit);
+		let api_version = api
+			.api_version::<dyn ProfileStorageRuntimeApi<Block, AccountId, Hash, BlockNumber>>(&at)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(2202), // No real reason for this value
+				message: "Something wrong".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		// Runtimes before version 2 only return available pros, without their tier, completeness
+		// and response metrics.
+		let runtime_api_result = if api_version.map_or(false, |x| x >= 2) {
+			api.get_pros(&at, profession, area, offset, limit, include_unavailable.unwrap_or(false))
+		} else {
+			api.get_pros_before_version_2(&at, profession, area, offset, limit)
+				.map(|x| x.into_iter().map(Into::into).collect())
+		};
 		runtime_api_result.map_err(|e| RpcError {
 			code: ErrorCode::ServerError(2202), // No real reason for this value
 			message: "Something wrong".into(),
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn get_profile_media(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		account_id: AccountId,
+	) -> Result<Vec<Media>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_profile_media(&at, account_id);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2204), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_private_field(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		owner: AccountId,
+		field: PrivateField,
+		viewer: AccountId,
+	) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_private_field(&at, owner, field, viewer);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2203), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
 }
@@ -3,18 +3,28 @@
 #![allow(clippy::unnecessary_mut_passed)]
 
 use codec::{Decode, Encode};
-use pallet_profile::rpc::ProProfile;
-use rp_profile::{Area, Profession};
+use pallet_profile::rpc::{ProProfile, ProProfileV1};
+use rp_profile::{Area, Media, PrivateField, Profession};
 use scale_info::TypeInfo;
 use sp_std::prelude::*;
 
 sp_api::decl_runtime_apis! {
-	pub trait ProfileApi<AccountId, Hash> where
+	#[api_version(2)]
+	pub trait ProfileApi<AccountId, Hash, BlockNumber> where
 		AccountId: Encode + Decode  + Clone + PartialEq + TypeInfo,
 		Hash: Encode + Decode + Default + Clone + PartialEq + TypeInfo,
+		BlockNumber: Encode + Decode + Clone + PartialEq + TypeInfo,
 	{
-		fn get_pros(profession: Profession, area: Area, offset: u64, limit: u16) -> Vec<ProProfile<AccountId>>;
+		#[changed_in(2)]
+		fn get_pros(profession: Profession, area: Area, offset: u64, limit: u16) -> Vec<ProProfileV1<AccountId>>;
+
+		/// Unavailable pros are only included if `include_unavailable` is set.
+		fn get_pros(profession: Profession, area: Area, offset: u64, limit: u16, include_unavailable: bool) -> Vec<ProProfile<AccountId, BlockNumber>>;
 
 		fn is_username_exist(username: Hash) -> bool;
+
+		fn get_profile_media(account_id: AccountId) -> Vec<Media>;
+
+		fn get_private_field(owner: AccountId, field: PrivateField, viewer: AccountId) -> Option<(Vec<u8>, Vec<u8>)>;
 	}
 }
@@ -13,24 +13,135 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
 pub mod rpc;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{pallet_prelude::*, sp_runtime::traits::BlockNumberProvider};
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::{
+			traits::{BlockNumberProvider, Dispatchable, Saturating},
+			Percent,
+		},
+		traits::{
+			schedule::{DispatchTime, Named as ScheduleNamed, LOWEST_PRIORITY},
+			Currency, ExistenceRequirement, OnUnbalanced, WithdrawReasons,
+		},
+	};
 	use frame_system::pallet_prelude::*;
-	use rp_profile::{Area, Content, NewProfile, Pro, Profession, SocialAccount};
-	use scale_info::prelude::vec::Vec;
+	use rp_profile::{
+		Area, Content, EncryptedField, Media, NewProfile, PrivateField, Pro, Profession, Profile,
+		SocialAccount, Subscription, SubscriptionTier,
+	};
+	use scale_info::prelude::{boxed::Box, vec, vec::Vec};
+
+	const AVAILABILITY_ID: &[u8; 12] = b"availability";
+	const SUBSCRIPTION_ID: &[u8; 12] = b"subscription";
+
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// Tells how quickly and how often an account answers connection requests.
+	pub trait ResponseMetrics<AccountId, BlockNumber> {
+		fn median_response_time(account: &AccountId) -> Option<BlockNumber>;
+
+		fn response_rate(account: &AccountId) -> Option<Percent>;
+	}
+
+	impl<AccountId, BlockNumber> ResponseMetrics<AccountId, BlockNumber> for () {
+		fn median_response_time(_account: &AccountId) -> Option<BlockNumber> {
+			None
+		}
+
+		fn response_rate(_account: &AccountId) -> Option<Percent> {
+			None
+		}
+	}
+
+	/// Tells who an account may share its private fields with, e.g. its connections.
+	pub trait FieldViewers<AccountId> {
+		fn can_view(owner: &AccountId, viewer: &AccountId) -> bool;
+	}
+
+	impl<AccountId> FieldViewers<AccountId> for () {
+		fn can_view(_owner: &AccountId, _viewer: &AccountId) -> bool {
+			false
+		}
+	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The runtime call type, used to schedule calls of this pallet.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin>
+			+ From<Call<Self>>
+			+ IsType<<Self as frame_system::Config>::Call>;
+
+		/// The caller origin, overarching type of all pallets origins.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// The scheduler used to restore the availability of pros when their vacation ends and
+		/// to renew subscriptions.
+		type Scheduler: ScheduleNamed<
+			Self::BlockNumber,
+			<Self as Config>::Call,
+			Self::PalletsOrigin,
+		>;
+
+		/// The currency subscription fees are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Handler for the paid subscription fees, usually the treasury.
+		type SubscriptionFees: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// How long a paid subscription lasts before it is renewed.
+		#[pallet::constant]
+		type SubscriptionPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of media entries attached to a profile.
+		#[pallet::constant]
+		type MaxMediaItems: Get<u32>;
+
+		/// Maximum length of a media caption.
+		#[pallet::constant]
+		type MaxCaptionLength: Get<u32>;
+
+		/// Maximum length of an encrypted profile field.
+		#[pallet::constant]
+		type MaxCiphertextLength: Get<u32>;
+
+		/// Maximum length of a field key wrapped for a single viewer.
+		#[pallet::constant]
+		type MaxWrappedKeyLength: Get<u32>;
+
+		/// Maximum number of viewers a private field can be shared with.
+		#[pallet::constant]
+		type MaxFieldViewers: Get<u32>;
+
+		/// Maximum encoded size of the structured profile of an account.
+		#[pallet::constant]
+		type MaxProfileDetailsLength: Get<u32>;
+
+		/// Responsiveness of pros, shown in their profiles.
+		type ResponseMetrics: ResponseMetrics<Self::AccountId, Self::BlockNumber>;
+
+		/// Decides who private fields can be shared with.
+		type FieldViewers: FieldViewers<Self::AccountId>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -52,6 +163,114 @@ pub mod pallet {
 	pub type Usernames<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, T::Hash, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn profile_details)]
+	pub type ProfileDetails<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Profile<T::Hash>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn verified_accounts)]
+	pub type VerifiedAccounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Share of the structured profile that is filled in, see `Pallet::update_completeness`.
+	#[pallet::storage]
+	#[pallet::getter(fn completeness)]
+	pub type Completeness<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Percent, ValueQuery>;
+
+	/// Raw key in `Pros` after which the completeness backfill resumes. Set while the backfill
+	/// started by the v1 migration is running.
+	#[pallet::storage]
+	#[pallet::getter(fn completeness_cursor)]
+	pub type CompletenessCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Ordered media entries of a profile. At most one of them is flagged as the cover.
+	#[pallet::storage]
+	#[pallet::getter(fn profile_media)]
+	pub type ProfileMedia<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<Media, T::MaxMediaItems>,
+		ValueQuery,
+	>;
+
+	/// Pros that are currently unavailable, with the block their availability is restored at.
+	#[pallet::storage]
+	#[pallet::getter(fn unavailable_pros)]
+	pub type UnavailablePros<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Option<T::BlockNumber>, OptionQuery>;
+
+	/// Fee per `SubscriptionPeriod`. Tiers without a fee cannot be subscribed to.
+	#[pallet::storage]
+	#[pallet::getter(fn tier_fees)]
+	pub type TierFees<T: Config> =
+		StorageMap<_, Blake2_128Concat, SubscriptionTier, BalanceOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn subscriptions)]
+	pub type Subscriptions<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Subscription<T::BlockNumber>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn private_fields)]
+	pub type PrivateFields<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		PrivateField,
+		EncryptedField<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// Never decreases, so keys granted for a removed or replaced ciphertext stay unusable.
+	#[pallet::storage]
+	#[pallet::getter(fn field_key_versions)]
+	pub type FieldKeyVersions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		PrivateField,
+		u32,
+		ValueQuery,
+	>;
+
+	/// (owner, field) => viewer => (key_version, wrapped_key)
+	#[pallet::storage]
+	#[pallet::getter(fn field_access)]
+	pub type FieldAccess<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(T::AccountId, PrivateField),
+		Blake2_128Concat,
+		T::AccountId,
+		(u32, Vec<u8>),
+		OptionQuery,
+	>;
+
+	/// (owner, field) => number of viewers in `FieldAccess`
+	#[pallet::storage]
+	#[pallet::getter(fn field_viewer_count)]
+	pub type FieldViewerCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, PrivateField), u32, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate::<T>()
+		}
+
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			match Self::completeness_cursor() {
+				Some(cursor) => crate::migrations::v1::backfill_batch::<T>(cursor),
+				None => T::DbWeight::get().reads(1),
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -63,6 +282,34 @@ pub mod pallet {
 		ProCreated(T::AccountId, Profession),
 		/// [account_id]
 		ProUpdated(T::AccountId),
+		/// [account_id, verified]
+		AccountVerified(T::AccountId, bool),
+		/// [account_id, index]
+		MediaAdded(T::AccountId, u32),
+		/// [account_id]
+		MediaReordered(T::AccountId),
+		/// [account_id, index]
+		MediaRemoved(T::AccountId, u32),
+		/// [account_id, available, until]
+		ProAvailabilityChanged(T::AccountId, bool, Option<T::BlockNumber>),
+		/// [tier, fee]
+		TierFeeSet(SubscriptionTier, Option<BalanceOf<T>>),
+		/// [account_id, tier, expires, fee]
+		Subscribed(T::AccountId, SubscriptionTier, T::BlockNumber, BalanceOf<T>),
+		/// [account_id, tier, expires, fee]
+		SubscriptionRenewed(T::AccountId, SubscriptionTier, T::BlockNumber, BalanceOf<T>),
+		/// [account_id, auto_renew]
+		SubscriptionAutoRenewSet(T::AccountId, bool),
+		/// [account_id, tier]
+		SubscriptionLapsed(T::AccountId, SubscriptionTier),
+		/// [account_id, field]
+		PrivateFieldUpdated(T::AccountId, PrivateField),
+		/// [account_id, field]
+		PrivateFieldRemoved(T::AccountId, PrivateField),
+		/// [account_id, field, viewer]
+		FieldAccessGranted(T::AccountId, PrivateField, T::AccountId),
+		/// [account_id, field, viewer]
+		FieldAccessRevoked(T::AccountId, PrivateField, T::AccountId),
 	}
 
 	#[pallet::error]
@@ -78,6 +325,22 @@ pub mod pallet {
 		InvalidAreas,
 		ProAccountNeeded,
 		ProCountsOverflow,
+		EmptyCiphertext,
+		EmptyWrappedKey,
+		PrivateFieldNotFound,
+		FieldAccessNotFound,
+		KeyVersionOverflow,
+		TooManyFieldViewers,
+		ViewerNotConnected,
+		PrivateFieldInPlainText,
+		ProfileDetailsTooLong,
+		MediaLimitReached,
+		MediaNotFound,
+		InvalidMediaOrder,
+		InvalidAvailabilityPeriod,
+		FailedToSchedule,
+		TierNotAvailable,
+		SubscriptionNotFound,
 	}
 
 	#[pallet::call]
@@ -186,6 +449,7 @@ pub mod pallet {
 				Pros::<T>::insert(&sender, (profession, areas));
 			}
 
+			Self::update_completeness(&sender);
 			Self::deposit_event(Event::<T>::ProCreated(sender, profession));
 
 			Ok(())
@@ -212,6 +476,7 @@ pub mod pallet {
 				Pros::<T>::insert(&sender, (pro.0, Some(areas)));
 			}
 
+			Self::update_completeness(&sender);
 			Self::deposit_event(Event::<T>::ProUpdated(sender));
 
 			Ok(())
@@ -242,6 +507,455 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Merge `details` into the structured profile. Private fields have to be stored with
+		/// `set_private_field` instead.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 2))]
+		pub fn update_profile_details(
+			origin: OriginFor<T>,
+			details: Box<Profile<T::Hash>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				Self::get_or_new_social_account(&sender).profile.is_some(),
+				Error::<T>::AccountHasNoProfile
+			);
+			ensure!(details.plain_private_fields().is_empty(), Error::<T>::PrivateFieldInPlainText);
+
+			let merged = match Self::profile_details(&sender) {
+				Some(old) => Profile::merge(old, *details),
+				None => *details,
+			};
+
+			ensure!(
+				merged.encoded_size() <= T::MaxProfileDetailsLength::get() as usize,
+				Error::<T>::ProfileDetailsTooLong
+			);
+
+			ProfileDetails::<T>::insert(&sender, merged);
+			Self::update_completeness(&sender);
+			Self::deposit_event(Event::<T>::ProfileUpdated(sender));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_verified(
+			origin: OriginFor<T>,
+			account_id: T::AccountId,
+			verified: bool,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			if verified {
+				VerifiedAccounts::<T>::insert(&account_id, true);
+			} else {
+				VerifiedAccounts::<T>::remove(&account_id);
+			}
+
+			Self::update_completeness(&account_id);
+			Self::deposit_event(Event::<T>::AccountVerified(account_id, verified));
+
+			Ok(())
+		}
+
+		/// Append a media entry. Flagging it as the cover unflags the previous cover.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn add_media(
+			origin: OriginFor<T>,
+			content: Content,
+			caption: BoundedVec<u8, T::MaxCaptionLength>,
+			cover: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(content.is_ipfs() && content.is_valid(), Error::<T>::InvalidContent);
+			ensure!(
+				Self::get_or_new_social_account(&sender).profile.is_some(),
+				Error::<T>::AccountHasNoProfile
+			);
+
+			let index = ProfileMedia::<T>::try_mutate(&sender, |media| -> Result<u32, Error<T>> {
+				if cover {
+					media.iter_mut().for_each(|x| x.cover = false);
+				}
+
+				media
+					.try_push(Media { content, caption: caption.into_inner(), cover })
+					.map_err(|_| Error::<T>::MediaLimitReached)?;
+
+				Ok(media.len() as u32 - 1)
+			})?;
+
+			Self::deposit_event(Event::<T>::MediaAdded(sender, index));
+
+			Ok(())
+		}
+
+		/// Reorder the media entries. `order` lists the current indexes in their new order.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn reorder_media(origin: OriginFor<T>, order: Vec<u32>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ProfileMedia::<T>::try_mutate(&sender, |media| -> DispatchResult {
+				ensure!(order.len() == media.len(), Error::<T>::InvalidMediaOrder);
+
+				let mut seen = vec![false; media.len()];
+				let mut reordered = Vec::with_capacity(media.len());
+
+				for index in order {
+					let item = media.get(index as usize).ok_or(Error::<T>::InvalidMediaOrder)?;
+
+					ensure!(!seen[index as usize], Error::<T>::InvalidMediaOrder);
+					seen[index as usize] = true;
+					reordered.push(item.clone());
+				}
+
+				*media =
+					BoundedVec::try_from(reordered).map_err(|_| Error::<T>::InvalidMediaOrder)?;
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::MediaReordered(sender));
+
+			Ok(())
+		}
+
+		#[pallet::weight((
+		100_000,
+		DispatchClass::Normal,
+		Pays::No
+		))]
+		pub fn remove_media(origin: OriginFor<T>, index: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ProfileMedia::<T>::try_mutate(&sender, |media| -> DispatchResult {
+				ensure!((index as usize) < media.len(), Error::<T>::MediaNotFound);
+
+				media.remove(index as usize);
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::MediaRemoved(sender, index));
+
+			Ok(())
+		}
+
+		/// Mark the sender as (un)available. An unavailable pro with an `until` block is made
+		/// available again by the scheduler once that block is reached.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 3))]
+		pub fn set_pro_availability(
+			origin: OriginFor<T>,
+			available: bool,
+			until: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Pros::<T>::contains_key(&sender), Error::<T>::ProAccountNeeded);
+
+			let schedule_id = Self::availability_schedule_id(&sender);
+
+			if let Some(Some(_)) = Self::unavailable_pros(&sender) {
+				let _ = T::Scheduler::cancel_named(schedule_id.clone());
+			}
+
+			if available {
+				ensure!(until.is_none(), Error::<T>::InvalidAvailabilityPeriod);
+
+				UnavailablePros::<T>::remove(&sender);
+			} else {
+				if let Some(until) = until {
+					ensure!(
+						until > <frame_system::Pallet<T>>::current_block_number(),
+						Error::<T>::InvalidAvailabilityPeriod
+					);
+
+					T::Scheduler::schedule_named(
+						schedule_id,
+						DispatchTime::At(until),
+						None,
+						LOWEST_PRIORITY,
+						frame_system::RawOrigin::Root.into(),
+						Call::restore_pro_availability { pro: sender.clone() }.into(),
+					)
+					.map_err(|_| Error::<T>::FailedToSchedule)?;
+				}
+
+				UnavailablePros::<T>::insert(&sender, until);
+			}
+
+			Self::deposit_event(Event::<T>::ProAvailabilityChanged(sender, available, until));
+
+			Ok(())
+		}
+
+		#[pallet::weight((
+		100_000,
+		DispatchClass::Operational,
+		Pays::No
+		))]
+		pub fn restore_pro_availability(origin: OriginFor<T>, pro: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			if UnavailablePros::<T>::contains_key(&pro) {
+				UnavailablePros::<T>::remove(&pro);
+				Self::deposit_event(Event::<T>::ProAvailabilityChanged(pro, true, None));
+			}
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_tier_fee(
+			origin: OriginFor<T>,
+			tier: SubscriptionTier,
+			fee: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			match fee {
+				Some(fee) => TierFees::<T>::insert(&tier, fee),
+				None => TierFees::<T>::remove(&tier),
+			}
+
+			Self::deposit_event(Event::<T>::TierFeeSet(tier, fee));
+
+			Ok(())
+		}
+
+		/// Pay for `tier` for one `SubscriptionPeriod`. A running subscription switches to `tier`
+		/// and is extended from its expiry, so the time already paid for is kept.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn subscribe(
+			origin: OriginFor<T>,
+			tier: SubscriptionTier,
+			auto_renew: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Pros::<T>::contains_key(&sender), Error::<T>::ProAccountNeeded);
+			let fee = Self::tier_fees(&tier).ok_or(Error::<T>::TierNotAvailable)?;
+
+			let schedule_id = Self::subscription_schedule_id(&sender);
+			let now = <frame_system::Pallet<T>>::current_block_number();
+
+			let starts = match Self::subscriptions(&sender) {
+				Some(current) => {
+					let _ = T::Scheduler::cancel_named(schedule_id.clone());
+
+					current.expires.max(now)
+				},
+				None => now,
+			};
+
+			Self::charge_subscription_fee(&sender, fee)?;
+
+			let expires = starts.saturating_add(T::SubscriptionPeriod::get());
+
+			T::Scheduler::schedule_named(
+				schedule_id,
+				DispatchTime::At(expires),
+				None,
+				LOWEST_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				Call::renew_subscription { pro: sender.clone() }.into(),
+			)
+			.map_err(|_| Error::<T>::FailedToSchedule)?;
+
+			Subscriptions::<T>::insert(&sender, Subscription { tier, expires, auto_renew });
+			Self::deposit_event(Event::<T>::Subscribed(sender, tier, expires, fee));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_auto_renew(origin: OriginFor<T>, auto_renew: bool) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Subscriptions::<T>::try_mutate(&sender, |x| -> DispatchResult {
+				let subscription = x.as_mut().ok_or(Error::<T>::SubscriptionNotFound)?;
+				subscription.auto_renew = auto_renew;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::SubscriptionAutoRenewSet(sender, auto_renew));
+
+			Ok(())
+		}
+
+		/// Called by the scheduler when a subscription expires. Charges the next period if
+		/// auto renewal is on and the pro can pay for it, otherwise the pro is downgraded.
+		#[pallet::weight((
+		10_000 + T::DbWeight::get().reads_writes(3, 2),
+		DispatchClass::Operational
+		))]
+		pub fn renew_subscription(origin: OriginFor<T>, pro: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let mut subscription = match Self::subscriptions(&pro) {
+				Some(subscription) => subscription,
+				None => return Ok(()),
+			};
+
+			let fee = Self::tier_fees(&subscription.tier);
+			let renewed = subscription.auto_renew
+				&& Pros::<T>::contains_key(&pro)
+				&& fee.map_or(false, |fee| Self::charge_subscription_fee(&pro, fee).is_ok());
+
+			if !renewed {
+				Subscriptions::<T>::remove(&pro);
+				Self::deposit_event(Event::<T>::SubscriptionLapsed(pro, subscription.tier));
+
+				return Ok(());
+			}
+
+			subscription.expires =
+				subscription.expires.saturating_add(T::SubscriptionPeriod::get());
+
+			let scheduled = T::Scheduler::schedule_named(
+				Self::subscription_schedule_id(&pro),
+				DispatchTime::At(subscription.expires),
+				None,
+				LOWEST_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				Call::renew_subscription { pro: pro.clone() }.into(),
+			);
+
+			// The period is already paid for, so a failed schedule must not revert the charge.
+			// `active_subscription` stops counting the tier once `expires` has passed.
+			if scheduled.is_err() {
+				subscription.auto_renew = false;
+			}
+
+			Subscriptions::<T>::insert(&pro, &subscription);
+			Self::deposit_event(Event::<T>::SubscriptionRenewed(
+				pro,
+				subscription.tier,
+				subscription.expires,
+				fee.unwrap_or_default(),
+			));
+
+			Ok(())
+		}
+
+		/// Store `field` encrypted. Every update rotates the key version, so access has to be
+		/// granted again for the new ciphertext.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(9, 4))]
+		pub fn set_private_field(
+			origin: OriginFor<T>,
+			field: PrivateField,
+			ciphertext: BoundedVec<u8, T::MaxCiphertextLength>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!ciphertext.is_empty(), Error::<T>::EmptyCiphertext);
+			ensure!(
+				Self::get_or_new_social_account(&sender).profile.is_some(),
+				Error::<T>::AccountHasNoProfile
+			);
+
+			let key_version = Self::field_key_versions(&sender, &field)
+				.checked_add(1)
+				.ok_or(Error::<T>::KeyVersionOverflow)?;
+
+			PrivateFields::<T>::insert(
+				&sender,
+				&field,
+				EncryptedField {
+					ciphertext: ciphertext.into_inner(),
+					key_version,
+					updated: <frame_system::Pallet<T>>::current_block_number(),
+				},
+			);
+			FieldKeyVersions::<T>::insert(&sender, &field, key_version);
+			Self::update_completeness(&sender);
+			Self::deposit_event(Event::<T>::PrivateFieldUpdated(sender, field));
+
+			Ok(())
+		}
+
+		/// Also drops every key granted for the field.
+		#[pallet::weight((
+		100_000 + T::DbWeight::get().writes(T::MaxFieldViewers::get() as u64 + 2),
+		DispatchClass::Normal,
+		Pays::No
+		))]
+		pub fn remove_private_field(origin: OriginFor<T>, field: PrivateField) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				PrivateFields::<T>::contains_key(&sender, &field),
+				Error::<T>::PrivateFieldNotFound
+			);
+
+			let key = (sender.clone(), field);
+
+			let _ = FieldAccess::<T>::clear_prefix(&key, T::MaxFieldViewers::get(), None);
+			FieldViewerCount::<T>::remove(&key);
+			PrivateFields::<T>::remove(&sender, &field);
+			Self::update_completeness(&sender);
+			Self::deposit_event(Event::<T>::PrivateFieldRemoved(sender, field));
+
+			Ok(())
+		}
+
+		/// Shares the current key of `field` with `viewer`, who has to be allowed by
+		/// `FieldViewers`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 2))]
+		pub fn grant_field_access(
+			origin: OriginFor<T>,
+			field: PrivateField,
+			viewer: T::AccountId,
+			wrapped_key: BoundedVec<u8, T::MaxWrappedKeyLength>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!wrapped_key.is_empty(), Error::<T>::EmptyWrappedKey);
+			ensure!(T::FieldViewers::can_view(&sender, &viewer), Error::<T>::ViewerNotConnected);
+			let encrypted =
+				Self::private_fields(&sender, &field).ok_or(Error::<T>::PrivateFieldNotFound)?;
+
+			let key = (&sender, field);
+
+			if !FieldAccess::<T>::contains_key(key, &viewer) {
+				let count = Self::field_viewer_count(key);
+
+				ensure!(count < T::MaxFieldViewers::get(), Error::<T>::TooManyFieldViewers);
+
+				FieldViewerCount::<T>::insert(key, count + 1);
+			}
+
+			FieldAccess::<T>::insert(
+				key,
+				&viewer,
+				(encrypted.key_version, wrapped_key.into_inner()),
+			);
+			Self::deposit_event(Event::<T>::FieldAccessGranted(sender, field, viewer));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn revoke_field_access(
+			origin: OriginFor<T>,
+			field: PrivateField,
+			viewer: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let key = (&sender, field);
+
+			ensure!(FieldAccess::<T>::contains_key(key, &viewer), Error::<T>::FieldAccessNotFound);
+
+			FieldAccess::<T>::remove(key, &viewer);
+			FieldViewerCount::<T>::mutate(key, |x| *x = x.saturating_sub(1));
+			Self::deposit_event(Event::<T>::FieldAccessRevoked(sender, field, viewer));
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -266,6 +980,62 @@ pub mod pallet {
 			Self::social_account_by_id(account_id)
 		}
 
+		pub fn is_pro_available(account_id: &T::AccountId) -> bool {
+			match Self::unavailable_pros(account_id) {
+				None => true,
+				Some(None) => false,
+				Some(Some(until)) => until <= <frame_system::Pallet<T>>::current_block_number(),
+			}
+		}
+
+		pub fn active_subscription(account_id: &T::AccountId) -> Option<SubscriptionTier> {
+			Self::subscriptions(account_id)
+				.filter(|x| x.expires > <frame_system::Pallet<T>>::current_block_number())
+				.map(|x| x.tier)
+		}
+
+		/// Recompute the completeness of `account_id`. Filled profile fields, including the
+		/// encrypted ones, make up 70%, service areas and verification 15% each.
+		pub fn update_completeness(account_id: &T::AccountId) {
+			let filled = Self::profile_details(account_id).map_or(0, |x| x.filled_fields())
+				+ PrivateFields::<T>::iter_key_prefix(account_id).count() as u32;
+			let has_areas =
+				Self::pros(account_id).and_then(|x| x.1).map_or(false, |x| !x.is_empty());
+
+			let mut score =
+				filled.min(Profile::<T::Hash>::FIELDS) * 70 / Profile::<T::Hash>::FIELDS;
+
+			if has_areas {
+				score += 15;
+			}
+
+			if Self::verified_accounts(account_id) {
+				score += 15;
+			}
+
+			Completeness::<T>::insert(account_id, Percent::from_percent(score as u8));
+		}
+
+		fn availability_schedule_id(account_id: &T::AccountId) -> Vec<u8> {
+			(AVAILABILITY_ID, account_id).encode()
+		}
+
+		fn subscription_schedule_id(account_id: &T::AccountId) -> Vec<u8> {
+			(SUBSCRIPTION_ID, account_id).encode()
+		}
+
+		fn charge_subscription_fee(account_id: &T::AccountId, fee: BalanceOf<T>) -> DispatchResult {
+			let imbalance = T::Currency::withdraw(
+				account_id,
+				fee,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			T::SubscriptionFees::on_unbalanced(imbalance);
+
+			Ok(())
+		}
+
 		fn should_increase_pro_cnt(
 			account_id: &T::AccountId,
 			profession: &Profession,
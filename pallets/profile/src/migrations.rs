@@ -0,0 +1,59 @@
+use crate::{CompletenessCursor, Config, Pallet, Pros};
+use frame_support::{
+	storage::StoragePrefixedMap,
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+use scale_info::prelude::vec::Vec;
+
+/// Runs every migration the on chain storage version has not seen yet.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight = T::DbWeight::get().reads(1);
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+	}
+
+	weight
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// Pros whose completeness is recomputed per block.
+	const BATCH_SIZE: u32 = 100;
+
+	/// Completeness scores are only written by the extrinsics that change them, so pros who
+	/// registered before they existed rank as empty profiles. There can be too many pros for a
+	/// single block, so the upgrade only sets the cursor and `backfill_batch` scores them from
+	/// `on_initialize`.
+	pub fn migrate<T: Config>() -> Weight {
+		CompletenessCursor::<T>::put(Pros::<T>::final_prefix().to_vec());
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().writes(2)
+	}
+
+	/// Scores up to `BATCH_SIZE` pros after `cursor` and clears the cursor once every pro is done.
+	pub fn backfill_batch<T: Config>(cursor: Vec<u8>) -> Weight {
+		let mut accounts = Pros::<T>::iter_keys_from(cursor);
+		let mut count: u64 = 0;
+
+		while count < BATCH_SIZE as u64 {
+			match accounts.next() {
+				Some(account_id) => {
+					Pallet::<T>::update_completeness(&account_id);
+					count += 1;
+				},
+				None => {
+					CompletenessCursor::<T>::kill();
+					return T::DbWeight::get().reads_writes(1 + count * 6, count + 1);
+				},
+			}
+		}
+
+		CompletenessCursor::<T>::put(accounts.last_raw_key().to_vec());
+
+		T::DbWeight::get().reads_writes(1 + count * 6, count + 1)
+	}
+}
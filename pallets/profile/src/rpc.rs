@@ -1,7 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-use crate::{Config, Pallet, Pros};
+use crate::{Config, Pallet, Pros, ResponseMetrics};
 use codec::{Decode, Encode};
-use rp_profile::{Area, Content, Profession};
+use frame_support::{sp_runtime::Percent, sp_std::cmp::Reverse};
+use rp_profile::{Area, Content, Media, PrivateField, Profession, SubscriptionTier};
 use scale_info::prelude::vec::Vec;
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
@@ -9,12 +10,45 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Encode, Decode, Clone, PartialEq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
-pub struct ProProfile<AccountId> {
+pub struct ProProfile<AccountId, BlockNumber> {
 	pub id: AccountId,
 	pub cid: Vec<u8>,
 	pub followers_count: u32,
 	pub following_accounts_count: u16,
 	pub reputation: u32,
+	pub tier: Option<SubscriptionTier>,
+	pub completeness: Percent,
+	/// Median number of blocks the pro takes to answer a connection request.
+	pub median_response_time: Option<BlockNumber>,
+	/// Share of the connection requests the pro answered.
+	pub response_rate: Option<Percent>,
+}
+
+/// `ProProfile` as returned by version 1 of the runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ProProfileV1<AccountId> {
+	pub id: AccountId,
+	pub cid: Vec<u8>,
+	pub followers_count: u32,
+	pub following_accounts_count: u16,
+	pub reputation: u32,
+}
+
+impl<AccountId, BlockNumber> From<ProProfileV1<AccountId>> for ProProfile<AccountId, BlockNumber> {
+	fn from(pro: ProProfileV1<AccountId>) -> Self {
+		Self {
+			id: pro.id,
+			cid: pro.cid,
+			followers_count: pro.followers_count,
+			following_accounts_count: pro.following_accounts_count,
+			reputation: pro.reputation,
+			tier: None,
+			completeness: Percent::default(),
+			median_response_time: None,
+			response_rate: None,
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -23,8 +57,9 @@ impl<T: Config> Pallet<T> {
 		area: Area,
 		offset: u64,
 		limit: u16,
-	) -> Vec<ProProfile<T::AccountId>> {
-		let pro_ids = Pros::<T>::iter()
+		include_unavailable: bool,
+	) -> Vec<ProProfile<T::AccountId, T::BlockNumber>> {
+		let mut pro_ids = Pros::<T>::iter()
 			.filter(|x| x.1 .0 == profession)
 			.filter(|x| {
 				if let Some(areas) = &x.1 .1 {
@@ -32,23 +67,33 @@ impl<T: Config> Pallet<T> {
 				}
 				false
 			})
-			.map(|x| x.0)
-			.collect::<Vec<T::AccountId>>();
+			.filter(|x| include_unavailable || Self::is_pro_available(&x.0))
+			.map(|x| (Self::active_subscription(&x.0), Self::completeness(&x.0), x.0))
+			.collect::<Vec<(Option<SubscriptionTier>, Percent, T::AccountId)>>();
+
+		// Paid tiers are listed first, highest tier on top, then the most complete profiles.
+		pro_ids.sort_by_key(|x| Reverse((x.0, x.1)));
 
-		let mut pros = Vec::<ProProfile<T::AccountId>>::new();
+		let mut pros = Vec::<ProProfile<T::AccountId, T::BlockNumber>>::new();
 
 		for i in offset as usize.. {
 			match pro_ids.get(i) {
-				Some(pro_id) => {
+				Some((tier, completeness, pro_id)) => {
 					if let Some(pro) = Self::get_social_account(&pro_id) {
 						if let Some(profile) = pro.profile {
 							if let Content::IPFS(cid) = profile.content {
 								pros.push(ProProfile {
-									id: pro_ids[i].clone(),
+									id: pro_id.clone(),
 									cid,
 									followers_count: pro.followers_count,
 									following_accounts_count: pro.following_accounts_count,
 									reputation: pro.reputation,
+									tier: *tier,
+									completeness: *completeness,
+									median_response_time: T::ResponseMetrics::median_response_time(
+										pro_id,
+									),
+									response_rate: T::ResponseMetrics::response_rate(pro_id),
 								});
 							}
 						}
@@ -64,4 +109,25 @@ impl<T: Config> Pallet<T> {
 
 		pros
 	}
+
+	pub fn get_profile_media(account_id: T::AccountId) -> Vec<Media> {
+		Self::profile_media(&account_id).into_inner()
+	}
+
+	/// Returns the ciphertext of `field` together with the key wrapped for `viewer`, if the
+	/// viewer has been granted access to the current key version.
+	pub fn get_private_field(
+		owner: T::AccountId,
+		field: PrivateField,
+		viewer: T::AccountId,
+	) -> Option<(Vec<u8>, Vec<u8>)> {
+		let encrypted = Self::private_fields(&owner, &field)?;
+		let (key_version, wrapped_key) = Self::field_access((&owner, field), &viewer)?;
+
+		if key_version != encrypted.key_version {
+			return None;
+		}
+
+		Some((encrypted.ciphertext, wrapped_key))
+	}
 }
@@ -0,0 +1,435 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::{traits::Hash, TokenError},
+		traits::{Currency, OnUnbalanced, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use rp_profile::{Area, Content};
+	use scale_info::prelude::vec::Vec;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum PropertyType {
+		House,
+		Apartment,
+		Townhouse,
+		Land,
+		Commercial,
+		Other,
+	}
+
+	#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct PropertyInfo<AccountId> {
+		pub owner: AccountId,
+		/// Description, photos and documents of the property.
+		pub content: Content,
+		pub area: Area,
+		pub property_type: PropertyType,
+	}
+
+	/// Ownership tokens of properties, e.g. NFTs of `pallet_uniques`. Whoever holds the token of
+	/// a property is its owner.
+	pub trait PropertyTokens<PropertyId, AccountId> {
+		/// Sets up the collection the tokens are minted in. Called once, at genesis or by the
+		/// upgrade that adds the pallet.
+		fn create_collection() -> DispatchResult;
+
+		fn holder(property_id: &PropertyId) -> Option<AccountId>;
+
+		fn mint(property_id: &PropertyId, owner: &AccountId) -> DispatchResult;
+
+		/// Stores the content CID and area of the property as attributes of its token.
+		fn set_metadata(property_id: &PropertyId, content: &Content, area: Area) -> DispatchResult;
+	}
+
+	impl<PropertyId, AccountId> PropertyTokens<PropertyId, AccountId> for () {
+		fn create_collection() -> DispatchResult {
+			Ok(())
+		}
+
+		fn holder(_property_id: &PropertyId) -> Option<AccountId> {
+			None
+		}
+
+		fn mint(_property_id: &PropertyId, _owner: &AccountId) -> DispatchResult {
+			Err(TokenError::Unsupported.into())
+		}
+
+		fn set_metadata(
+			_property_id: &PropertyId,
+			_content: &Content,
+			_area: Area,
+		) -> DispatchResult {
+			Err(TokenError::Unsupported.into())
+		}
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Settles ownership disputes by reassigning properties, e.g. the council.
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+		#[pallet::constant]
+		type MaxAddressLength: Get<u32>;
+
+		type PropertyTokens: PropertyTokens<Self::Hash, Self::AccountId>;
+
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Reserved from whoever registers a property for as long as it stays registered. It moves
+		/// to the new owner with every transfer.
+		#[pallet::constant]
+		type PropertyDeposit: Get<BalanceOf<Self>>;
+
+		/// Receives the deposits of registrations overturned by `ForceOrigin`.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self {}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			T::PropertyTokens::create_collection().expect("Property collection can be created");
+		}
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Self::on_chain_storage_version() >= STORAGE_VERSION {
+				return T::DbWeight::get().reads(1);
+			}
+
+			// Only fails if the collection is taken already, in which case no token can be minted.
+			let _ = T::PropertyTokens::create_collection();
+			STORAGE_VERSION.put::<Self>();
+
+			// The storage version, plus the collection and its owner's index.
+			T::DbWeight::get().reads_writes(2, 3)
+		}
+	}
+
+	/// Hash of the normalized address => property
+	#[pallet::storage]
+	#[pallet::getter(fn properties)]
+	pub type Properties<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::Hash, PropertyInfo<T::AccountId>, OptionQuery>;
+
+	/// Properties whose ownership token was minted. They belong to whoever holds the token, and
+	/// to nobody once it is burnt.
+	#[pallet::storage]
+	#[pallet::getter(fn is_tokenized)]
+	pub type Tokenized<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, bool, ValueQuery>;
+
+	/// property => (depositor, deposit)
+	#[pallet::storage]
+	#[pallet::getter(fn property_deposits)]
+	pub type PropertyDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::Hash, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// [property, owner]
+		PropertyRegistered(T::Hash, T::AccountId),
+		/// [property]
+		PropertyUpdated(T::Hash),
+		/// [property, from, to]
+		PropertyTransferred(T::Hash, T::AccountId, T::AccountId),
+		/// [property, owner]
+		PropertyTokenMinted(T::Hash, T::AccountId),
+		/// [property, registrant, amount]
+		PropertyDepositSlashed(T::Hash, T::AccountId, BalanceOf<T>),
+		/// [property, owner]
+		PropertyUnregistered(T::Hash, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		PropertyAlreadyRegistered,
+		PropertyNotFound,
+		NotPropertyOwner,
+		InvalidContent,
+		EmptyAddress,
+		PropertyAlreadyTokenized,
+		PropertyTokenized,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Registers the property at `address` with the sender as its owner. Its id is the hash
+		/// of the normalized address, see `normalize_address`. `PropertyDeposit` is reserved from
+		/// the sender, so that squatting addresses has a cost.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn register_property(
+			origin: OriginFor<T>,
+			address: BoundedVec<u8, T::MaxAddressLength>,
+			content: Content,
+			area: Area,
+			property_type: PropertyType,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(content.is_valid(), Error::<T>::InvalidContent);
+
+			let normalized = normalize_address(&address);
+
+			ensure!(!normalized.is_empty(), Error::<T>::EmptyAddress);
+
+			let property_id = T::Hashing::hash(&normalized);
+
+			ensure!(
+				!Properties::<T>::contains_key(&property_id),
+				Error::<T>::PropertyAlreadyRegistered
+			);
+
+			let deposit = T::PropertyDeposit::get();
+
+			T::Currency::reserve(&sender, deposit)?;
+
+			PropertyDeposits::<T>::insert(&property_id, (&sender, deposit));
+			Properties::<T>::insert(
+				&property_id,
+				PropertyInfo { owner: sender.clone(), content, area, property_type },
+			);
+			Self::deposit_event(Event::<T>::PropertyRegistered(property_id, sender));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn update_property(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			content: Content,
+			area: Area,
+			property_type: PropertyType,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(content.is_valid(), Error::<T>::InvalidContent);
+
+			let mut property =
+				Self::properties(&property_id).ok_or(Error::<T>::PropertyNotFound)?;
+
+			ensure!(Self::is_owner(&property_id, &sender), Error::<T>::NotPropertyOwner);
+
+			if T::PropertyTokens::holder(&property_id).is_some() {
+				T::PropertyTokens::set_metadata(&property_id, &content, area)?;
+			}
+
+			property.content = content;
+			property.area = area;
+			property.property_type = property_type;
+
+			Properties::<T>::insert(&property_id, property);
+			Self::deposit_event(Event::<T>::PropertyUpdated(property_id));
+
+			Ok(())
+		}
+
+		/// Hands the sender's property to `new_owner`. The registration deposit moves along: it
+		/// is reserved from `new_owner` and returned to whoever held it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 4))]
+		pub fn transfer_property(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let property = Self::properties(&property_id).ok_or(Error::<T>::PropertyNotFound)?;
+
+			ensure!(property.owner == sender, Error::<T>::NotPropertyOwner);
+			ensure!(!Self::is_tokenized(&property_id), Error::<T>::PropertyTokenized);
+
+			let deposit = T::PropertyDeposit::get();
+
+			T::Currency::reserve(&new_owner, deposit)?;
+
+			if let Some((depositor, amount)) = PropertyDeposits::<T>::mutate(&property_id, |x| {
+				x.replace((new_owner.clone(), deposit))
+			}) {
+				T::Currency::unreserve(&depositor, amount);
+			}
+
+			Self::set_owner(property_id, property, new_owner);
+
+			Ok(())
+		}
+
+		/// Removes the sender's property from the registry and returns the registration deposit.
+		/// Tokenized properties stay registered, since their token refers to them.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn unregister_property(origin: OriginFor<T>, property_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let property = Self::properties(&property_id).ok_or(Error::<T>::PropertyNotFound)?;
+
+			ensure!(property.owner == sender, Error::<T>::NotPropertyOwner);
+			ensure!(!Self::is_tokenized(&property_id), Error::<T>::PropertyTokenized);
+
+			if let Some((depositor, amount)) = PropertyDeposits::<T>::take(&property_id) {
+				T::Currency::unreserve(&depositor, amount);
+			}
+
+			Properties::<T>::remove(&property_id);
+			Self::deposit_event(Event::<T>::PropertyUnregistered(property_id, sender));
+
+			Ok(())
+		}
+
+		/// Hands a disputed property to its rightful owner. If its owner is the one who paid the
+		/// registration deposit, the deposit is slashed, since they claimed a property that was
+		/// not theirs. Otherwise it is returned. A property whose token was burnt is handed over
+		/// as an untokenized one.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 4))]
+		pub fn force_transfer_property(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let property = Self::properties(&property_id).ok_or(Error::<T>::PropertyNotFound)?;
+
+			ensure!(
+				T::PropertyTokens::holder(&property_id).is_none(),
+				Error::<T>::PropertyTokenized
+			);
+
+			// The token may have changed hands before it was burnt, so its last holder is unknown.
+			let was_tokenized = Tokenized::<T>::take(&property_id);
+
+			if let Some((registrant, amount)) = PropertyDeposits::<T>::take(&property_id) {
+				if !was_tokenized && registrant == property.owner {
+					let (imbalance, _) = T::Currency::slash_reserved(&registrant, amount);
+					T::Slashed::on_unbalanced(imbalance);
+					Self::deposit_event(Event::<T>::PropertyDepositSlashed(
+						property_id,
+						registrant,
+						amount,
+					));
+				} else {
+					T::Currency::unreserve(&registrant, amount);
+				}
+			}
+
+			Self::set_owner(property_id, property, new_owner);
+
+			Ok(())
+		}
+
+		/// Mints the ownership token of the sender's property. From then on the property changes
+		/// hands with its token.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 5))]
+		pub fn mint_property_token(origin: OriginFor<T>, property_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let property = Self::properties(&property_id).ok_or(Error::<T>::PropertyNotFound)?;
+
+			ensure!(property.owner == sender, Error::<T>::NotPropertyOwner);
+			ensure!(!Self::is_tokenized(&property_id), Error::<T>::PropertyAlreadyTokenized);
+
+			T::PropertyTokens::mint(&property_id, &sender)?;
+			T::PropertyTokens::set_metadata(&property_id, &property.content, property.area)?;
+
+			Tokenized::<T>::insert(&property_id, true);
+
+			Self::deposit_event(Event::<T>::PropertyTokenMinted(property_id, sender));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Id of the property at `address`, whether it is registered or not.
+		pub fn property_id(address: &[u8]) -> T::Hash {
+			T::Hashing::hash(&normalize_address(address))
+		}
+
+		/// The holder of the property's token if it was tokenized, the registered owner otherwise.
+		pub fn owner_of(property_id: &T::Hash) -> Option<T::AccountId> {
+			if Self::is_tokenized(property_id) {
+				T::PropertyTokens::holder(property_id)
+			} else {
+				Self::properties(property_id).map(|x| x.owner)
+			}
+		}
+
+		pub fn is_owner(property_id: &T::Hash, who: &T::AccountId) -> bool {
+			Self::owner_of(property_id).map_or(false, |x| &x == who)
+		}
+
+		fn set_owner(
+			property_id: T::Hash,
+			mut property: PropertyInfo<T::AccountId>,
+			new_owner: T::AccountId,
+		) {
+			let old_owner = property.owner;
+
+			property.owner = new_owner.clone();
+
+			Properties::<T>::insert(&property_id, property);
+			Self::deposit_event(Event::<T>::PropertyTransferred(property_id, old_owner, new_owner));
+		}
+	}
+
+	/// Lowercases ASCII letters, turns punctuation into spaces and collapses whitespace, so that
+	/// spelling variants of the same address get the same id.
+	pub fn normalize_address(address: &[u8]) -> Vec<u8> {
+		let mut normalized = Vec::with_capacity(address.len());
+
+		for &byte in address {
+			if byte.is_ascii_whitespace() || byte.is_ascii_punctuation() {
+				if normalized.last().map_or(false, |x| *x != b' ') {
+					normalized.push(b' ');
+				}
+			} else {
+				normalized.push(byte.to_ascii_lowercase());
+			}
+		}
+
+		if normalized.last() == Some(&b' ') {
+			normalized.pop();
+		}
+
+		normalized
+	}
+}
@@ -0,0 +1,169 @@
+use crate as pallet_property;
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{ConstU16, ConstU32, ConstU64, GenesisBuild},
+	weights::constants::RocksDbWeight,
+};
+use frame_system::{self as system, EnsureRoot};
+use rp_profile::{Area, Content};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
+};
+use std::{cell::RefCell, collections::BTreeMap};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Property: pallet_property::{Pallet, Call, Storage, Config, Event<T>},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = RocksDbWeight;
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static COLLECTION: RefCell<bool> = RefCell::new(false);
+	static TOKENS: RefCell<BTreeMap<H256, u64>> = RefCell::new(BTreeMap::new());
+	static METADATA: RefCell<BTreeMap<H256, (Content, Area)>> = RefCell::new(BTreeMap::new());
+}
+
+/// Keeps the tokens in memory. Like the runtime, it only mints once the property collection
+/// exists.
+pub struct MockTokens;
+
+impl MockTokens {
+	pub fn collection_exists() -> bool {
+		COLLECTION.with(|x| *x.borrow())
+	}
+
+	pub fn destroy_collection() {
+		COLLECTION.with(|x| *x.borrow_mut() = false);
+	}
+
+	pub fn metadata(property_id: &H256) -> Option<(Content, Area)> {
+		METADATA.with(|x| x.borrow().get(property_id).cloned())
+	}
+
+	pub fn transfer(property_id: &H256, to: u64) {
+		TOKENS.with(|x| x.borrow_mut().insert(*property_id, to));
+	}
+
+	pub fn burn(property_id: &H256) {
+		TOKENS.with(|x| x.borrow_mut().remove(property_id));
+	}
+}
+
+impl pallet_property::PropertyTokens<H256, u64> for MockTokens {
+	fn create_collection() -> DispatchResult {
+		COLLECTION.with(|x| {
+			if *x.borrow() {
+				return Err(DispatchError::Other("InUse"));
+			}
+
+			*x.borrow_mut() = true;
+
+			Ok(())
+		})
+	}
+
+	fn holder(property_id: &H256) -> Option<u64> {
+		TOKENS.with(|x| x.borrow().get(property_id).copied())
+	}
+
+	fn mint(property_id: &H256, owner: &u64) -> DispatchResult {
+		if !Self::collection_exists() {
+			return Err(DispatchError::Other("PropertyCollectionNotOwned"));
+		}
+
+		TOKENS.with(|x| x.borrow_mut().insert(*property_id, *owner));
+
+		Ok(())
+	}
+
+	fn set_metadata(property_id: &H256, content: &Content, area: Area) -> DispatchResult {
+		METADATA.with(|x| x.borrow_mut().insert(*property_id, (content.clone(), area)));
+
+		Ok(())
+	}
+}
+
+impl pallet_property::Config for Test {
+	type Event = Event;
+	type ForceOrigin = EnsureRoot<u64>;
+	type MaxAddressLength = ConstU32<64>;
+	type PropertyTokens = MockTokens;
+	type Currency = Balances;
+	type PropertyDeposit = ConstU64<10>;
+	type Slashed = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	COLLECTION.with(|x| *x.borrow_mut() = false);
+	TOKENS.with(|x| x.borrow_mut().clear());
+	METADATA.with(|x| x.borrow_mut().clear());
+
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100), (3, 100), (4, 5)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+	GenesisBuild::<Test>::assimilate_storage(
+		&pallet_property::GenesisConfig::default(),
+		&mut storage,
+	)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
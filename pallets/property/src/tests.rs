@@ -0,0 +1,319 @@
+use crate::{mock::*, normalize_address, Error, PropertyInfo, PropertyType};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchError,
+	traits::{
+		ConstU32, Currency, Get, GetStorageVersion, Hooks, ReservableCurrency, StorageVersion,
+	},
+	BoundedVec,
+};
+use rp_profile::Content;
+use sp_core::H256;
+
+fn address(address: &[u8]) -> BoundedVec<u8, ConstU32<64>> {
+	address.to_vec().try_into().unwrap()
+}
+
+fn register(who: u64, at: &[u8]) -> H256 {
+	assert_ok!(Property::register_property(
+		Origin::signed(who),
+		address(at),
+		Content::None,
+		120,
+		PropertyType::House,
+	));
+
+	Property::property_id(at)
+}
+
+#[test]
+fn normalize_address_ignores_case_punctuation_and_spacing() {
+	assert_eq!(normalize_address(b"  12, Baker St.  "), b"12 baker st".to_vec());
+	assert_eq!(normalize_address(b"12 BAKER\tst"), b"12 baker st".to_vec());
+	assert_eq!(normalize_address(b"12-Baker--St"), b"12 baker st".to_vec());
+	assert_eq!(normalize_address(b" ,.; "), Vec::<u8>::new());
+}
+
+#[test]
+fn register_property_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let property_id = register(1, b"12 Baker St");
+
+		assert_eq!(property_id, Property::property_id(b"12, baker st."));
+		assert_eq!(
+			Property::properties(property_id),
+			Some(PropertyInfo {
+				owner: 1,
+				content: Content::None,
+				area: 120,
+				property_type: PropertyType::House,
+			})
+		);
+		assert_eq!(Property::property_deposits(property_id), Some((1, 10)));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Property::owner_of(&property_id), Some(1));
+	});
+}
+
+#[test]
+fn register_property_rejects_spelling_variants_and_invalid_input() {
+	new_test_ext().execute_with(|| {
+		register(1, b"12 Baker St");
+
+		assert_noop!(
+			Property::register_property(
+				Origin::signed(2),
+				address(b"12 BAKER ST."),
+				Content::None,
+				80,
+				PropertyType::Apartment,
+			),
+			Error::<Test>::PropertyAlreadyRegistered
+		);
+		assert_noop!(
+			Property::register_property(
+				Origin::signed(2),
+				address(b" - "),
+				Content::None,
+				80,
+				PropertyType::Apartment,
+			),
+			Error::<Test>::EmptyAddress
+		);
+		assert_noop!(
+			Property::register_property(
+				Origin::signed(2),
+				address(b"1 Main St"),
+				Content::IPFS(b"cid".to_vec()),
+				80,
+				PropertyType::Apartment,
+			),
+			Error::<Test>::InvalidContent
+		);
+	});
+}
+
+#[test]
+fn register_property_requires_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert!(Property::register_property(
+			Origin::signed(4),
+			address(b"12 Baker St"),
+			Content::None,
+			120,
+			PropertyType::House,
+		)
+		.is_err());
+		assert_eq!(Property::properties(Property::property_id(b"12 Baker St")), None);
+	});
+}
+
+#[test]
+fn transfer_property_moves_the_deposit_to_the_new_owner() {
+	new_test_ext().execute_with(|| {
+		let property_id = register(1, b"12 Baker St");
+
+		assert_noop!(
+			Property::transfer_property(Origin::signed(2), property_id, 2),
+			Error::<Test>::NotPropertyOwner
+		);
+		assert!(Property::transfer_property(Origin::signed(1), property_id, 4).is_err());
+
+		assert_ok!(Property::transfer_property(Origin::signed(1), property_id, 2));
+
+		assert_eq!(Property::owner_of(&property_id), Some(2));
+		assert_eq!(Property::property_deposits(property_id), Some((2, 10)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 10);
+	});
+}
+
+#[test]
+fn unregister_property_refunds_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let property_id = register(1, b"12 Baker St");
+		assert_ok!(Property::transfer_property(Origin::signed(1), property_id, 2));
+
+		assert_noop!(
+			Property::unregister_property(Origin::signed(1), property_id),
+			Error::<Test>::NotPropertyOwner
+		);
+
+		assert_ok!(Property::unregister_property(Origin::signed(2), property_id));
+
+		assert_eq!(Property::properties(property_id), None);
+		assert_eq!(Property::property_deposits(property_id), None);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 100);
+
+		// The address is free again.
+		register(3, b"12 Baker St");
+	});
+}
+
+#[test]
+fn force_transfer_property_slashes_the_registrant() {
+	new_test_ext().execute_with(|| {
+		let property_id = register(1, b"12 Baker St");
+
+		assert!(Property::force_transfer_property(Origin::signed(1), property_id, 2).is_err());
+
+		assert_ok!(Property::force_transfer_property(Origin::root(), property_id, 2));
+
+		assert_eq!(Property::owner_of(&property_id), Some(2));
+		assert_eq!(Property::property_deposits(property_id), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 90);
+	});
+}
+
+#[test]
+fn force_transfer_property_spares_an_honest_registrant() {
+	new_test_ext().execute_with(|| {
+		let property_id = register(1, b"12 Baker St");
+		assert_ok!(Property::transfer_property(Origin::signed(1), property_id, 2));
+
+		assert_ok!(Property::force_transfer_property(Origin::root(), property_id, 3));
+
+		assert_eq!(Property::owner_of(&property_id), Some(3));
+		// The registrant sold the property, so they keep their funds.
+		assert_eq!(Balances::free_balance(1), 100);
+		// The disputed owner held the deposit at the time, so it is theirs to lose.
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 90);
+	});
+}
+
+#[test]
+fn mint_property_token_hands_ownership_to_the_token() {
+	new_test_ext().execute_with(|| {
+		let property_id = register(1, b"12 Baker St");
+
+		assert_noop!(
+			Property::mint_property_token(Origin::signed(2), property_id),
+			Error::<Test>::NotPropertyOwner
+		);
+
+		assert_ok!(Property::mint_property_token(Origin::signed(1), property_id));
+
+		assert!(Property::is_tokenized(property_id));
+		assert_eq!(MockTokens::metadata(&property_id), Some((Content::None, 120)));
+		assert_noop!(
+			Property::mint_property_token(Origin::signed(1), property_id),
+			Error::<Test>::PropertyAlreadyTokenized
+		);
+
+		MockTokens::transfer(&property_id, 2);
+
+		assert_eq!(Property::owner_of(&property_id), Some(2));
+		assert!(Property::is_owner(&property_id, &2));
+		assert!(!Property::is_owner(&property_id, &1));
+		assert_noop!(
+			Property::transfer_property(Origin::signed(1), property_id, 3),
+			Error::<Test>::PropertyTokenized
+		);
+		assert_noop!(
+			Property::unregister_property(Origin::signed(1), property_id),
+			Error::<Test>::PropertyTokenized
+		);
+		assert_noop!(
+			Property::force_transfer_property(Origin::root(), property_id, 3),
+			Error::<Test>::PropertyTokenized
+		);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_reports_its_weight() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Property>();
+
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+
+		assert_eq!(Property::on_runtime_upgrade(), db_weight.reads_writes(2, 3));
+		assert_eq!(Property::on_chain_storage_version(), StorageVersion::new(1));
+		assert_eq!(Property::on_runtime_upgrade(), db_weight.reads(1));
+	});
+}
+
+#[test]
+fn deposits_are_reserved_not_spent() {
+	new_test_ext().execute_with(|| {
+		register(1, b"12 Baker St");
+		register(1, b"14 Baker St");
+
+		assert_eq!(Balances::reserved_balance(1), 20);
+		assert_eq!(Balances::total_balance(&1), 100);
+		assert!(Balances::can_reserve(&1, 80));
+	});
+}
+
+#[test]
+fn genesis_creates_the_property_collection_once() {
+	new_test_ext().execute_with(|| {
+		assert!(MockTokens::collection_exists());
+
+		// Already created, so the upgrade leaves it alone.
+		StorageVersion::new(0).put::<Property>();
+		Property::on_runtime_upgrade();
+
+		assert!(MockTokens::collection_exists());
+	});
+}
+
+#[test]
+fn mint_property_token_only_mints_into_the_property_collection() {
+	new_test_ext().execute_with(|| {
+		let property_id = register(1, b"12 Baker St");
+
+		MockTokens::destroy_collection();
+
+		assert_noop!(
+			Property::mint_property_token(Origin::signed(1), property_id),
+			DispatchError::Other("PropertyCollectionNotOwned")
+		);
+		assert!(!Property::is_tokenized(property_id));
+		assert_eq!(Property::owner_of(&property_id), Some(1));
+	});
+}
+
+#[test]
+fn burnt_property_token_leaves_the_property_ownerless_until_force_transferred() {
+	new_test_ext().execute_with(|| {
+		let property_id = register(1, b"12 Baker St");
+		assert_ok!(Property::mint_property_token(Origin::signed(1), property_id));
+		MockTokens::transfer(&property_id, 2);
+
+		MockTokens::burn(&property_id);
+
+		assert_eq!(Property::owner_of(&property_id), None);
+		assert!(!Property::is_owner(&property_id, &1));
+		assert!(!Property::is_owner(&property_id, &2));
+		assert_noop!(
+			Property::update_property(
+				Origin::signed(1),
+				property_id,
+				Content::None,
+				100,
+				PropertyType::House,
+			),
+			Error::<Test>::NotPropertyOwner
+		);
+		assert_noop!(
+			Property::transfer_property(Origin::signed(1), property_id, 3),
+			Error::<Test>::PropertyTokenized
+		);
+
+		assert_ok!(Property::force_transfer_property(Origin::root(), property_id, 3));
+
+		assert!(!Property::is_tokenized(property_id));
+		assert_eq!(Property::owner_of(&property_id), Some(3));
+		// The token may have been sold before it was burnt, so the minter is not slashed.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+
+		// Handed over as an untokenized property, which can be tokenized again.
+		assert_ok!(Property::mint_property_token(Origin::signed(3), property_id));
+		assert_eq!(Property::owner_of(&property_id), Some(3));
+	});
+}
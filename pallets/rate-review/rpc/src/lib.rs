@@ -0,0 +1,236 @@
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_rate_review::{
+	rpc::{RatingSummary, ReviewSort, ReviewView},
+	ReviewSubject,
+};
+pub use rate_review_runtime_api::RateReviewApi as RateReviewRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::scale_info::TypeInfo;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc]
+pub trait RateReviewApi<BlockHash, AccountId, Hash, BlockNumber, Balance> {
+	#[rpc(name = "rateReview_getProRating")]
+	fn get_pro_rating(&self, at: Option<BlockHash>, pro_id: AccountId) -> Result<RatingSummary>;
+	#[rpc(name = "rateReview_getPropertyRating")]
+	fn get_property_rating(
+		&self,
+		at: Option<BlockHash>,
+		property_id: Hash,
+	) -> Result<RatingSummary>;
+	#[rpc(name = "rateReview_getProDimensionRatings")]
+	fn get_pro_dimension_ratings(
+		&self,
+		at: Option<BlockHash>,
+		pro_id: AccountId,
+	) -> Result<Vec<(Vec<u8>, RatingSummary)>>;
+	#[rpc(name = "rateReview_getProReviews")]
+	fn get_pro_reviews(
+		&self,
+		at: Option<BlockHash>,
+		pro_id: AccountId,
+		sort: Option<ReviewSort>,
+		verified_only: Option<bool>,
+		offset: u64,
+		limit: u16,
+	) -> Result<Vec<ReviewView<AccountId, BlockNumber, Balance>>>;
+	#[rpc(name = "rateReview_getPropertyReviews")]
+	fn get_property_reviews(
+		&self,
+		at: Option<BlockHash>,
+		property_id: Hash,
+		sort: Option<ReviewSort>,
+		verified_only: Option<bool>,
+		offset: u64,
+		limit: u16,
+	) -> Result<Vec<ReviewView<AccountId, BlockNumber, Balance>>>;
+	#[rpc(name = "rateReview_getReviewerReviews")]
+	fn get_reviewer_reviews(
+		&self,
+		at: Option<BlockHash>,
+		reviewer: AccountId,
+		offset: u64,
+		limit: u16,
+	) -> Result<Vec<(ReviewSubject<AccountId, Hash>, ReviewView<AccountId, BlockNumber, Balance>)>>;
+	#[rpc(name = "rateReview_getTipsReceived")]
+	fn get_tips_received(&self, at: Option<BlockHash>, account: AccountId) -> Result<Balance>;
+}
+
+pub struct RateReview<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> RateReview<C, P> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Hash, BlockNumber, Balance>
+	RateReviewApi<<Block as BlockT>::Hash, AccountId, Hash, BlockNumber, Balance>
+	for RateReview<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: RateReviewRuntimeApi<Block, AccountId, Hash, BlockNumber, Balance>,
+	AccountId: Encode + Decode + Clone + PartialEq + TypeInfo,
+	Hash: Encode + Decode + Clone + PartialEq + TypeInfo,
+	BlockNumber: Encode + Decode + Clone + PartialEq + TypeInfo,
+	Balance: Encode + Decode + Clone + PartialEq + TypeInfo,
+{
+	fn get_pro_rating(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		pro_id: AccountId,
+	) -> Result<RatingSummary> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_pro_rating(&at, pro_id);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2301), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_property_rating(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		property_id: Hash,
+	) -> Result<RatingSummary> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_property_rating(&at, property_id);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2302), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pro_reviews(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		pro_id: AccountId,
+		sort: Option<ReviewSort>,
+		verified_only: Option<bool>,
+		offset: u64,
+		limit: u16,
+	) -> Result<Vec<ReviewView<AccountId, BlockNumber, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_pro_reviews(
+			&at,
+			pro_id,
+			sort.unwrap_or(ReviewSort::Newest),
+			verified_only.unwrap_or_default(),
+			offset,
+			limit,
+		);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2303), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_property_reviews(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		property_id: Hash,
+		sort: Option<ReviewSort>,
+		verified_only: Option<bool>,
+		offset: u64,
+		limit: u16,
+	) -> Result<Vec<ReviewView<AccountId, BlockNumber, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_property_reviews(
+			&at,
+			property_id,
+			sort.unwrap_or(ReviewSort::Newest),
+			verified_only.unwrap_or_default(),
+			offset,
+			limit,
+		);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2304), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_reviewer_reviews(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		reviewer: AccountId,
+		offset: u64,
+		limit: u16,
+	) -> Result<Vec<(ReviewSubject<AccountId, Hash>, ReviewView<AccountId, BlockNumber, Balance>)>>
+	{
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_reviewer_reviews(&at, reviewer, offset, limit);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2305), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pro_dimension_ratings(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		pro_id: AccountId,
+	) -> Result<Vec<(Vec<u8>, RatingSummary)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_pro_dimension_ratings(&at, pro_id);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2306), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_tips_received(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+		account: AccountId,
+	) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_api_result = api.get_tips_received(&at, account);
+		runtime_api_result.map_err(|e| RpcError {
+			code: ErrorCode::ServerError(2307), // No real reason for this value
+			message: "Something wrong".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
@@ -0,0 +1,34 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Decode, Encode};
+use pallet_rate_review::{
+	rpc::{RatingSummary, ReviewSort, ReviewView},
+	ReviewSubject,
+};
+use scale_info::TypeInfo;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait RateReviewApi<AccountId, Hash, BlockNumber, Balance> where
+		AccountId: Encode + Decode + Clone + PartialEq + TypeInfo,
+		Hash: Encode + Decode + Clone + PartialEq + TypeInfo,
+		BlockNumber: Encode + Decode + Clone + PartialEq + TypeInfo,
+		Balance: Encode + Decode + Clone + PartialEq + TypeInfo,
+	{
+		fn get_pro_rating(pro_id: AccountId) -> RatingSummary;
+
+		fn get_property_rating(property_id: Hash) -> RatingSummary;
+
+		fn get_pro_dimension_ratings(pro_id: AccountId) -> Vec<(Vec<u8>, RatingSummary)>;
+
+		fn get_pro_reviews(pro_id: AccountId, sort: ReviewSort, verified_only: bool, offset: u64, limit: u16) -> Vec<ReviewView<AccountId, BlockNumber, Balance>>;
+
+		fn get_property_reviews(property_id: Hash, sort: ReviewSort, verified_only: bool, offset: u64, limit: u16) -> Vec<ReviewView<AccountId, BlockNumber, Balance>>;
+
+		fn get_reviewer_reviews(reviewer: AccountId, offset: u64, limit: u16) -> Vec<(ReviewSubject<AccountId, Hash>, ReviewView<AccountId, BlockNumber, Balance>)>;
+
+		fn get_tips_received(account: AccountId) -> Balance;
+	}
+}
@@ -14,37 +14,423 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod rpc;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{pallet_prelude::*, WeakBoundedVec};
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::{
+			traits::{AccountIdConversion, CheckedDiv, One, SaturatedConversion, Saturating, Zero},
+			FixedPointNumber, FixedU128, Perbill,
+		},
+		traits::{
+			Currency, ExistenceRequirement, OnUnbalanced, ReservableCurrency, StorageVersion,
+		},
+		PalletId, WeakBoundedVec,
+	};
 	use frame_system::pallet_prelude::*;
-	use scale_info::prelude::{vec, vec::Vec};
+	use rp_profile::Content;
+	use scale_info::prelude::vec::Vec;
+	#[cfg(feature = "std")]
+	use serde::{Deserialize, Serialize};
+
+	pub const MAX_RATE: u8 = 5;
+
+	/// Reputation at which a reviewer's reviews count twice as much as those of a newcomer.
+	const FULL_REPUTATION: u32 = 100;
+
+	/// How many times a review backed by a verified engagement counts in the weighted pro rating.
+	const VERIFIED_WEIGHT: u32 = 2;
 
-	const MAX_RATE: u8 = 5;
-	const LIKES_SEGMENT_LENGTH: u32 = 256;
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
-	type Rate = u8;
-	type Review<AccountId, MaxReviewLength> =
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	pub type Rate = u8;
+	/// A review in the legacy per-subject vectors.
+	pub type Review<AccountId, MaxReviewLength> =
 		(AccountId, Rate, WeakBoundedVec<u8, MaxReviewLength>);
+	/// Name of a rating dimension and its weight in the overall rate.
+	pub type RatingDimension<MaxNameLength> = (BoundedVec<u8, MaxNameLength>, u8);
+	/// A review together with the block it was first written at.
+	pub type ReviewEntry<BlockNumber, MaxReviewLength> =
+		(Rate, WeakBoundedVec<u8, MaxReviewLength>, BlockNumber);
+	/// A replaced version of a review and the block it was replaced at.
+	pub type ReviewEdit<BlockNumber, MaxReviewLength> =
+		(BlockNumber, Rate, WeakBoundedVec<u8, MaxReviewLength>);
+
+	/// Running totals of the rates a subject received. The average is derived from them on demand,
+	/// so it never loses precision no matter how many reviews are added, edited or removed.
+	#[derive(
+		Encode, Decode, Default, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+	)]
+	pub struct Rating {
+		pub sum: u64,
+		pub count: u32,
+		/// Number of reviews per rate, indexed by the rate itself.
+		pub histogram: [u32; MAX_RATE as usize + 1],
+	}
+
+	impl Rating {
+		/// Leaves the totals untouched when it fails.
+		pub fn add(&mut self, rate: Rate) -> Option<()> {
+			let sum = self.sum.checked_add(rate as u64)?;
+			let count = self.count.checked_add(1)?;
+			let bucket = self.histogram.get(rate as usize)?.checked_add(1)?;
+
+			self.sum = sum;
+			self.count = count;
+			self.histogram[rate as usize] = bucket;
+			Some(())
+		}
+
+		/// Leaves the totals untouched when it fails.
+		pub fn remove(&mut self, rate: Rate) -> Option<()> {
+			let sum = self.sum.checked_sub(rate as u64)?;
+			let count = self.count.checked_sub(1)?;
+			let bucket = self.histogram.get(rate as usize)?.checked_sub(1)?;
+
+			self.sum = sum;
+			self.count = count;
+			self.histogram[rate as usize] = bucket;
+			Some(())
+		}
+
+		/// Average truncated to a whole rate.
+		pub fn rate(&self) -> Rate {
+			self.sum.checked_div(self.count as u64).unwrap_or_default() as Rate
+		}
+
+		pub fn average(&self) -> FixedU128 {
+			if self.count == 0 {
+				return FixedU128::from_inner(0);
+			}
+
+			FixedU128::saturating_from_rational(self.sum, self.count)
+		}
+	}
+
+	/// What a review is about.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum ReviewSubject<AccountId, Hash> {
+		Pro(AccountId),
+		Property(Hash),
+	}
+
+	/// Who a tip goes to.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum TipTarget<AccountId, Hash> {
+		Pro(AccountId),
+		/// The author of the review of `subject` written by the given account.
+		Review(ReviewSubject<AccountId, Hash>, AccountId),
+	}
+
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum ReportReason {
+		Spam,
+		Abusive,
+		Defamatory,
+		Fake,
+		Other,
+	}
+
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum ModerationAction {
+		Hidden,
+		Restored,
+		ReportsDismissed,
+	}
+
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct ModerationRecord<AccountId, Hash, BlockNumber> {
+		pub subject: ReviewSubject<AccountId, Hash>,
+		pub reviewer: AccountId,
+		pub action: ModerationAction,
+		pub block: BlockNumber,
+	}
+
+	/// Hash of the engagement, signed by the pro as `(b"realm3/engagement", subject, reviewer,
+	/// hash)`. Binding the subject and a context keeps a signature from being replayed for
+	/// another review or accepted anywhere else the pro's key signs.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct EngagementAttestation<Hash, Signature>(pub Hash, pub Signature);
+
+	/// Tells which properties can be reviewed and who may answer their reviews. Reviews of ids
+	/// that do not exist stay readable, editable and removable, but take no new reviews.
+	pub trait PropertyOwnership<PropertyId, AccountId> {
+		fn exists(property_id: &PropertyId) -> bool;
+
+		fn is_owner(property_id: &PropertyId, who: &AccountId) -> bool;
+	}
+
+	impl<PropertyId, AccountId> PropertyOwnership<PropertyId, AccountId> for () {
+		fn exists(_property_id: &PropertyId) -> bool {
+			true
+		}
+
+		fn is_owner(_property_id: &PropertyId, _who: &AccountId) -> bool {
+			false
+		}
+	}
+
+	/// Looks up the profession a pro is registered with.
+	pub trait ProProfession<AccountId, Profession> {
+		fn profession(pro_id: &AccountId) -> Option<Profession>;
+	}
+
+	impl<AccountId, Profession> ProProfession<AccountId, Profession> for () {
+		fn profession(_pro_id: &AccountId) -> Option<Profession> {
+			None
+		}
+	}
+
+	/// Standing of a reviewer, used to weigh their reviews of pros.
+	pub trait ReviewerStanding<AccountId, BlockNumber> {
+		/// Block the reviewer joined at.
+		fn member_since(reviewer: &AccountId) -> Option<BlockNumber>;
+
+		fn reputation(reviewer: &AccountId) -> u32;
+
+		fn is_connected(reviewer: &AccountId, pro_id: &AccountId) -> bool;
+	}
+
+	impl<AccountId, BlockNumber> ReviewerStanding<AccountId, BlockNumber> for () {
+		fn member_since(_reviewer: &AccountId) -> Option<BlockNumber> {
+			None
+		}
+
+		fn reputation(_reviewer: &AccountId) -> u32 {
+			0
+		}
+
+		fn is_connected(_reviewer: &AccountId, _pro_id: &AccountId) -> bool {
+			false
+		}
+	}
+
+	/// Tells who may review a pro, e.g. only their clients.
+	pub trait ReviewEligibility<AccountId> {
+		fn can_review_pro(reviewer: &AccountId, pro_id: &AccountId) -> bool;
+	}
+
+	impl<AccountId> ReviewEligibility<AccountId> for () {
+		fn can_review_pro(_reviewer: &AccountId, _pro_id: &AccountId) -> bool {
+			true
+		}
+	}
+
+	/// Checks the engagement proofs attached to reviews.
+	pub trait VerifyEngagement<AccountId, Hash, Proof> {
+		fn verify(
+			reviewer: &AccountId,
+			subject: &ReviewSubject<AccountId, Hash>,
+			proof: &Proof,
+		) -> bool;
+	}
+
+	impl<AccountId, Hash, Proof> VerifyEngagement<AccountId, Hash, Proof> for () {
+		fn verify(
+			_reviewer: &AccountId,
+			_subject: &ReviewSubject<AccountId, Hash>,
+			_proof: &Proof,
+		) -> bool {
+			false
+		}
+	}
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
+		/// Decides which properties can be reviewed and who may reply to their reviews.
+		type PropertyOwnership: PropertyOwnership<Self::Hash, Self::AccountId>;
+
+		/// Decides who may review a pro.
+		type ReviewEligibility: ReviewEligibility<Self::AccountId>;
+
+		type EngagementProof: Parameter;
+
+		/// Decides which reviews are backed by a verified engagement.
+		type EngagementVerifier: VerifyEngagement<
+			Self::AccountId,
+			Self::Hash,
+			Self::EngagementProof,
+		>;
+
+		/// Hides reported reviews and restores them on appeal, e.g. the council.
+		type ModerationOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Number of reports after which a review is escalated to the moderation origin.
+		#[pallet::constant]
+		type ReportThreshold: Get<u32>;
+
+		/// How many accounts can report a single review. Bounds the cleanup of its reports, so it
+		/// should not be below `ReportThreshold`.
+		#[pallet::constant]
+		type MaxReportsPerReview: Get<u32>;
+
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Reserved from the reviewer for every new review.
+		#[pallet::constant]
+		type ReviewDeposit: Get<BalanceOf<Self>>;
+
+		/// How long a review deposit stays reserved before it can be released.
+		#[pallet::constant]
+		type DepositChallengePeriod: Get<Self::BlockNumber>;
+
+		/// Receives the deposits of reviews still hidden by moderation once their challenge period
+		/// is over.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Length of the period the per-account review limit applies to.
+		#[pallet::constant]
+		type ReviewPeriod: Get<Self::BlockNumber>;
+
+		/// How many reviews an account can write per period.
+		#[pallet::constant]
+		type MaxReviewsPerPeriod: Get<u32>;
+
+		/// Length of the window new reviews of a subject are counted over.
+		#[pallet::constant]
+		type SurgeWindow: Get<Self::BlockNumber>;
+
+		/// Number of new reviews within a window after which a subject is locked.
+		#[pallet::constant]
+		type SurgeThreshold: Get<u32>;
+
+		/// How long a subject takes no new reviews after a surge.
+		#[pallet::constant]
+		type SurgeLockPeriod: Get<Self::BlockNumber>;
+
+		/// Derives the reward pool account, which is funded by treasury spends.
+		#[pallet::constant]
+		type RewardPalletId: Get<PalletId>;
+
+		/// How often the reward pool is shared among helpful reviewers.
+		#[pallet::constant]
+		type RewardPeriod: Get<Self::BlockNumber>;
+
+		/// Likes a review has to earn within a period to get a share of the pool.
+		#[pallet::constant]
+		type RewardLikeThreshold: Get<u32>;
+
+		/// How many reviews are settled per block while the rewards of a period are paid out.
+		#[pallet::constant]
+		type MaxPayoutsPerBlock: Get<u32>;
+
+		#[pallet::constant]
+		type MaxTipMemoLength: Get<u32>;
+
+		type Profession: Parameter + MaxEncodedLen;
+
+		/// Picks the rating dimensions that apply to a pro.
+		type ProProfession: ProProfession<Self::AccountId, Self::Profession>;
+
+		#[pallet::constant]
+		type MaxRatingDimensions: Get<u32>;
+
+		#[pallet::constant]
+		type MaxDimensionNameLength: Get<u32>;
+
+		/// Weighs reviews in the weighted pro rating.
+		type ReviewerStanding: ReviewerStanding<Self::AccountId, Self::BlockNumber>;
+
+		/// Account age at which a reviewer gets their full weight.
+		#[pallet::constant]
+		type ReviewerMaturity: Get<Self::BlockNumber>;
+
+		/// Age at which a review counts half in the weighted pro rating. Zero disables the decay.
+		#[pallet::constant]
+		type RatingHalfLife: Get<Self::BlockNumber>;
+
 		#[pallet::constant]
 		type MaxReviewLength: Get<u32>;
 
 		#[pallet::constant]
 		type MaxReplyLength: Get<u32>;
+
+		/// How many times a review can be edited. Every replaced version is kept for moderation.
+		#[pallet::constant]
+		type MaxReviewEdits: Get<u32>;
+
+		/// How many legacy reviews are moved into the new layout per block during the migration.
+		#[pallet::constant]
+		type MigrationBatchSize: Get<u32>;
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self {}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			// The first period ends a full period after genesis rather than on the first block.
+			NextRewardAt::<T>::put(T::RewardPeriod::get());
+		}
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// property => reviewer => review
+	#[pallet::storage]
+	#[pallet::getter(fn property_reviews)]
+	pub type PropertyReviews<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::Hash,
+		Blake2_128Concat,
+		T::AccountId,
+		ReviewEntry<T::BlockNumber, T::MaxReviewLength>,
+		OptionQuery,
+	>;
+
+	/// pro => reviewer => review
+	#[pallet::storage]
+	#[pallet::getter(fn pro_reviews)]
+	pub type ProReviews<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		ReviewEntry<T::BlockNumber, T::MaxReviewLength>,
+		OptionQuery,
+	>;
+
+	/// reviewer => subject => block the review was created at
+	#[pallet::storage]
+	#[pallet::getter(fn reviews_by_reviewer)]
+	pub type ReviewsByReviewer<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		ReviewSubject<T::AccountId, T::Hash>,
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// Legacy layout, drained into `PropertyReviews` by the v3 migration.
 	#[pallet::storage]
-	#[pallet::getter(fn property_review)]
+	#[pallet::unbounded]
 	pub type PropertyReview<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
@@ -53,8 +439,9 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Legacy layout, drained into `ProReviews` by the v3 migration.
 	#[pallet::storage]
-	#[pallet::getter(fn pro_review)]
+	#[pallet::unbounded]
 	pub type ProReview<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
@@ -63,10 +450,29 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Set while legacy reviews are still being moved into the new layout. Reviews cannot be
+	/// changed until it is cleared.
+	#[pallet::storage]
+	#[pallet::getter(fn migration_pending)]
+	pub type MigrationPending<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Legacy reviews of the first remaining legacy subject that were already moved.
+	#[pallet::storage]
+	#[pallet::getter(fn migration_offset)]
+	pub type MigrationOffset<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn property_rating)]
+	pub type PropertyRating<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::Hash, Rating, ValueQuery>;
+
 	#[pallet::storage]
-	#[pallet::getter(fn property_rate)]
-	pub type PropertyRate<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, Rate, ValueQuery>;
+	#[pallet::getter(fn pro_rating)]
+	pub type ProRating<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Rating, ValueQuery>;
 
+	/// Overall rating of a pro truncated to a whole rate, kept in sync with `ProRating` for
+	/// clients reading the old layout.
 	#[pallet::storage]
 	#[pallet::getter(fn pro_rate)]
 	pub type ProRate<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Rate, ValueQuery>;
@@ -81,35 +487,115 @@ pub mod pallet {
 	pub type ProReviewsCnt<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
+	/// Dimensions pros of a profession are scored on. Changing them does not rewrite the scores
+	/// already given, which are matched to dimensions by position.
 	#[pallet::storage]
-	#[pallet::getter(fn property_reviews_likes)]
-	pub type PropertyReviewLikes<T: Config> = StorageMap<
+	#[pallet::getter(fn rating_dimensions)]
+	pub type RatingDimensions<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
-		T::Hash,
-		WeakBoundedVec<T::AccountId, ConstU32<LIKES_SEGMENT_LENGTH>>,
+		T::Profession,
+		BoundedVec<RatingDimension<T::MaxDimensionNameLength>, T::MaxRatingDimensions>,
+		ValueQuery,
+	>;
+
+	/// pro => reviewer => score per dimension
+	#[pallet::storage]
+	#[pallet::getter(fn pro_review_scores)]
+	pub type ProReviewScores<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<Rate, T::MaxRatingDimensions>,
+		OptionQuery,
+	>;
+
+	/// pro => dimension index => rating
+	#[pallet::storage]
+	#[pallet::getter(fn pro_dimension_rating)]
+	pub type ProDimensionRating<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		u32,
+		Rating,
 		ValueQuery,
 	>;
 
+	/// (property, reviewer) => who => liked
 	#[pallet::storage]
-	#[pallet::getter(fn pro_reviews_likes)]
-	pub type ProReviewsLikes<T: Config> = StorageMap<
+	#[pallet::getter(fn property_review_likers)]
+	pub type PropertyReviewLikers<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
+		(T::Hash, T::AccountId),
+		Blake2_128Concat,
 		T::AccountId,
-		WeakBoundedVec<T::AccountId, ConstU32<LIKES_SEGMENT_LENGTH>>,
+		bool,
 		ValueQuery,
 	>;
 
+	/// (pro, reviewer) => who => liked
 	#[pallet::storage]
-	#[pallet::getter(fn property_reviews_likes_cnt)]
-	pub type PropertyReviewsLikesCnt<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::Hash, u32, ValueQuery>;
+	#[pallet::getter(fn pro_review_likers)]
+	pub type ProReviewLikers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery,
+	>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn pro_reviews_likes_cnt)]
-	pub type ProReviewsLikesCnt<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+	#[pallet::getter(fn property_review_like_cnt)]
+	pub type PropertyReviewLikeCnt<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::Hash, T::AccountId), u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pro_review_like_cnt)]
+	pub type ProReviewLikeCnt<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::AccountId), u32, ValueQuery>;
+
+	/// (property, reviewer) => replaced versions, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn property_review_edits)]
+	pub type PropertyReviewEdits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::Hash, T::AccountId),
+		BoundedVec<ReviewEdit<T::BlockNumber, T::MaxReviewLength>, T::MaxReviewEdits>,
+		ValueQuery,
+	>;
+
+	/// (pro, reviewer) => replaced versions, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn pro_review_edits)]
+	pub type ProReviewEdits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		BoundedVec<ReviewEdit<T::BlockNumber, T::MaxReviewLength>, T::MaxReviewEdits>,
+		ValueQuery,
+	>;
+
+	/// (property, reviewer) => off chain body and media of the review
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn property_review_content)]
+	pub type PropertyReviewContent<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::Hash, T::AccountId), Content, OptionQuery>;
+
+	/// (pro, reviewer) => off chain body and media of the review
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn pro_review_content)]
+	pub type ProReviewContent<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::AccountId), Content, OptionQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn property_replies)]
@@ -141,176 +627,1669 @@ pub mod pallet {
 	pub type ProReplyCnt<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// [property, who]
-		PropertyReviewCreated(T::Hash, T::AccountId),
-		/// [pro, who]
-		ProReviewCreated(T::AccountId, T::AccountId),
-		/// [property, who]
-		PropertyReviewLikeCreated(T::Hash, T::AccountId),
-		/// [pro, who]
-		ProReviewLikeCreated(T::AccountId, T::AccountId),
-		/// [pro, replied, who]
-		PropertyReplyCreated(T::Hash, T::AccountId, T::AccountId),
-		/// [pro, replied, who]
-		ProReplyCreated(T::AccountId, T::AccountId, T::AccountId),
-	}
-
-	#[pallet::error]
-	pub enum Error<T> {
-		InvalidRate,
-		PropertyReviewAlreadyCreated,
-		ProReviewAlreadyCreated,
-		PropertyLikeLimitReached,
-		ProLikeLimitReached,
-		PropertyReviewsOverflow,
-		ProReviewsOverflow,
-		PropertyReviewAlreadyLiked,
-		ProReviewAlreadyLiked,
-		PropertyReviewsLikesOverflow,
-		ProReviewsLikesOverflow,
-		PropertyReplyOverflow,
-		ProReplyOverflow,
-	}
-
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		// -------------------- Property
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn create_property_review(
-			origin: OriginFor<T>,
-			property_id: T::Hash,
-			rate: Rate,
-			review: WeakBoundedVec<u8, T::MaxReviewLength>,
-		) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
+	/// (subject, reviewer) => reporter => reason
+	#[pallet::storage]
+	#[pallet::getter(fn reports)]
+	pub type Reports<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		Blake2_128Concat,
+		T::AccountId,
+		ReportReason,
+		OptionQuery,
+	>;
 
-			ensure!(rate <= MAX_RATE, Error::<T>::InvalidRate);
+	#[pallet::storage]
+	#[pallet::getter(fn report_cnt)]
+	pub type ReportCnt<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		u32,
+		ValueQuery,
+	>;
 
-			let mut reviews = Self::get_property_reviews(&property_id);
+	/// Reviews that reached the report threshold => block they were escalated at
+	#[pallet::storage]
+	#[pallet::getter(fn pending_moderation)]
+	pub type PendingModeration<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// Hidden reviews => block they were hidden at. They are left out of ratings and counts.
+	#[pallet::storage]
+	#[pallet::getter(fn hidden_reviews)]
+	pub type HiddenReviews<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// Reviews backed by a verified engagement => block they were verified at
+	#[pallet::storage]
+	#[pallet::getter(fn verified_reviews)]
+	pub type VerifiedReviews<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// (subject, reviewer) => (deposit, block it can be released at)
+	#[pallet::storage]
+	#[pallet::getter(fn review_deposits)]
+	pub type ReviewDeposits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		(BalanceOf<T>, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	/// reviewer => (end of the current period, reviews written in it)
+	#[pallet::storage]
+	#[pallet::getter(fn reviewer_activity)]
+	pub type ReviewerActivity<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+	/// subject => (end of the current window, new reviews in it)
+	#[pallet::storage]
+	#[pallet::getter(fn subject_activity)]
+	pub type SubjectActivity<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ReviewSubject<T::AccountId, T::Hash>,
+		(T::BlockNumber, u32),
+		ValueQuery,
+	>;
+
+	/// Subjects taking no new reviews after a surge => block the lock ends at
+	#[pallet::storage]
+	#[pallet::getter(fn surge_locks)]
+	pub type SurgeLocks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ReviewSubject<T::AccountId, T::Hash>,
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// recipient => sum of the tips they received
+	#[pallet::storage]
+	#[pallet::getter(fn tips_received)]
+	pub type TipsReceived<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// (subject, reviewer) => sum of the tips the review earned
+	#[pallet::storage]
+	#[pallet::getter(fn review_tips)]
+	pub type ReviewTips<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reward_period)]
+	pub type RewardPeriodIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_reward_at)]
+	pub type NextRewardAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// period => (subject, reviewer) => likes earned within the period
+	#[pallet::storage]
+	#[pallet::getter(fn period_likes)]
+	pub type PeriodLikes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Blake2_128Concat,
+		(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		u32,
+		ValueQuery,
+	>;
+
+	/// period => likes of the reviews that reached the threshold within it
+	#[pallet::storage]
+	#[pallet::getter(fn period_rewarded_likes)]
+	pub type PeriodRewardedLikes<T: Config> = StorageMap<_, Blake2_128Concat, u32, u32, ValueQuery>;
+
+	/// (period, pool balance to share, rewarded likes) of the period being paid out
+	#[pallet::storage]
+	#[pallet::getter(fn pending_payout)]
+	pub type PendingPayout<T: Config> = StorageValue<_, (u32, BalanceOf<T>, u32), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn moderation_log)]
+	pub type ModerationLog<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		ModerationRecord<T::AccountId, T::Hash, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn moderation_log_cnt)]
+	pub type ModerationLogCnt<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate::<T>()
+		}
+
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			if Self::migration_pending() {
+				return crate::migrations::v3::migrate_batch::<T>();
+			}
+
+			let weight = T::DbWeight::get().reads(3);
+
+			// A period is only closed once the previous one is fully paid out.
+			if let Some(payout) = Self::pending_payout() {
+				return weight.saturating_add(Self::pay_rewards(payout));
+			}
+
+			if n >= Self::next_reward_at() {
+				return weight.saturating_add(Self::close_reward_period(n));
+			}
+
+			weight
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// [property, who]
+		PropertyReviewCreated(T::Hash, T::AccountId),
+		/// [pro, who]
+		ProReviewCreated(T::AccountId, T::AccountId),
+		/// [property, who]
+		PropertyReviewUpdated(T::Hash, T::AccountId),
+		/// [pro, who]
+		ProReviewUpdated(T::AccountId, T::AccountId),
+		/// [property, who]
+		PropertyReviewRemoved(T::Hash, T::AccountId),
+		/// [pro, who]
+		ProReviewRemoved(T::AccountId, T::AccountId),
+		/// [property, reviewer, who]
+		PropertyReviewLikeCreated(T::Hash, T::AccountId, T::AccountId),
+		/// [pro, reviewer, who]
+		ProReviewLikeCreated(T::AccountId, T::AccountId, T::AccountId),
+		/// [property, reviewer, who]
+		PropertyReviewLikeRemoved(T::Hash, T::AccountId, T::AccountId),
+		/// [pro, reviewer, who]
+		ProReviewLikeRemoved(T::AccountId, T::AccountId, T::AccountId),
+		/// [pro, replied, who]
+		PropertyReplyCreated(T::Hash, T::AccountId, T::AccountId),
+		/// [pro, replied, who]
+		ProReplyCreated(T::AccountId, T::AccountId, T::AccountId),
+		/// [property, replied, who]
+		PropertyReplyUpdated(T::Hash, T::AccountId, T::AccountId),
+		/// [pro, replied]
+		ProReplyUpdated(T::AccountId, T::AccountId),
+		/// [property, replied, who]
+		PropertyReplyRemoved(T::Hash, T::AccountId, T::AccountId),
+		/// [pro, replied]
+		ProReplyRemoved(T::AccountId, T::AccountId),
+		/// [subject, reviewer, who, reason]
+		ReviewReported(
+			ReviewSubject<T::AccountId, T::Hash>,
+			T::AccountId,
+			T::AccountId,
+			ReportReason,
+		),
+		/// [subject, reviewer]
+		ReviewEscalated(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		/// [subject, reviewer]
+		ReviewHidden(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		/// [subject, reviewer]
+		ReviewRestored(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		/// [subject, reviewer]
+		ReportsDismissed(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		/// [subject, reviewer, amount]
+		DepositReleased(ReviewSubject<T::AccountId, T::Hash>, T::AccountId, BalanceOf<T>),
+		/// [subject, reviewer, amount]
+		DepositSlashed(ReviewSubject<T::AccountId, T::Hash>, T::AccountId, BalanceOf<T>),
+		/// [profession]
+		RatingDimensionsSet(T::Profession),
+		/// [subject, reviewer]
+		ReviewVerified(ReviewSubject<T::AccountId, T::Hash>, T::AccountId),
+		/// [who, target, amount, memo]
+		Tipped(
+			T::AccountId,
+			TipTarget<T::AccountId, T::Hash>,
+			BalanceOf<T>,
+			Option<BoundedVec<u8, T::MaxTipMemoLength>>,
+		),
+		/// [period, pot]
+		RewardPeriodClosed(u32, BalanceOf<T>),
+		/// [subject, reviewer, amount]
+		ReviewRewardPaid(ReviewSubject<T::AccountId, T::Hash>, T::AccountId, BalanceOf<T>),
+		/// [subject, until]
+		ReviewSurgeLocked(ReviewSubject<T::AccountId, T::Hash>, T::BlockNumber),
+		/// [subject]
+		ReviewSurgeLockLifted(ReviewSubject<T::AccountId, T::Hash>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		InvalidRate,
+		RatingOverflow,
+		PropertyReviewAlreadyCreated,
+		ProReviewAlreadyCreated,
+		PropertyReviewsOverflow,
+		ProReviewsOverflow,
+		PropertyReviewAlreadyLiked,
+		ProReviewAlreadyLiked,
+		PropertyReviewNotLiked,
+		ProReviewNotLiked,
+		CannotLikeOwnReview,
+		PropertyReviewsLikesOverflow,
+		ProReviewsLikesOverflow,
+		PropertyReplyOverflow,
+		ProReplyOverflow,
+		PropertyReviewNotFound,
+		ProReviewNotFound,
+		PropertyReplyAlreadyCreated,
+		ProReplyAlreadyCreated,
+		PropertyReplyNotFound,
+		ProReplyNotFound,
+		NotPropertyOwner,
+		ReviewEditLimitReached,
+		LikeWitnessTooLow,
+		MigrationInProgress,
+		CannotReviewSelf,
+		NotEligibleToReview,
+		ReviewNotFound,
+		ReviewAlreadyReported,
+		CannotReportOwnReview,
+		ReportsOverflow,
+		ReportLimitReached,
+		NoReports,
+		ReviewAlreadyHidden,
+		ReviewNotHidden,
+		ReviewIsHidden,
+		ModerationLogOverflow,
+		DepositNotFound,
+		ChallengePeriodNotOver,
+		ReviewUnderModeration,
+		InvalidScores,
+		InvalidDimensions,
+		InvalidContent,
+		ReviewLimitReached,
+		SubjectLocked,
+		SubjectNotLocked,
+		ReviewAlreadyVerified,
+		InvalidEngagementProof,
+		ZeroTip,
+		CannotTipSelf,
+		NotAPro,
+		PropertyNotFound,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		// -------------------- Property
+		/// `review` is a short summary kept on chain for indexing, `content` optionally points to
+		/// the full body and media stored off chain.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(10, 10))]
+		pub fn create_property_review(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			rate: Rate,
+			review: WeakBoundedVec<u8, T::MaxReviewLength>,
+			content: Content,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
 
+			ensure!(rate <= MAX_RATE, Error::<T>::InvalidRate);
+			ensure!(content.is_valid(), Error::<T>::InvalidContent);
+
+			Self::ensure_not_migrating()?;
+			ensure!(T::PropertyOwnership::exists(&property_id), Error::<T>::PropertyNotFound);
 			ensure!(
-				reviews.clone().iter().find(|&x| x.0 == sender).is_none(),
-				Error::<T>::PropertyReviewAlreadyCreated
+				!T::PropertyOwnership::is_owner(&property_id, &sender),
+				Error::<T>::CannotReviewSelf
 			);
 
-			reviews.push((sender.clone(), rate, review));
-
-			let new_rate = Self::add_to_rate(
-				Self::property_rate(&property_id),
-				rate,
-				Self::property_reviews_cnt(&property_id),
+			ensure!(
+				!PropertyReviews::<T>::contains_key(&property_id, &sender),
+				Error::<T>::PropertyReviewAlreadyCreated
 			);
+
+			let mut rating = Self::property_rating(&property_id);
+			rating.add(rate).ok_or(Error::<T>::RatingOverflow)?;
 			let new_cnt = Self::property_reviews_cnt(&property_id)
 				.checked_add(1)
 				.ok_or(Error::<T>::PropertyReviewsOverflow)?;
 
-			PropertyReview::<T>::insert(&property_id, reviews);
-			PropertyRate::<T>::insert(&property_id, new_rate);
+			let subject = ReviewSubject::Property(property_id);
+
+			Self::note_new_review(&subject, &sender)?;
+			Self::reserve_deposit(&subject, &sender)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			PropertyReviews::<T>::insert(&property_id, &sender, (rate, review, now));
+			ReviewsByReviewer::<T>::insert(&sender, &subject, now);
+			PropertyRating::<T>::insert(&property_id, rating);
 			PropertyReviewsCnt::<T>::insert(&property_id, new_cnt);
+			if !content.is_none() {
+				PropertyReviewContent::<T>::insert((&property_id, &sender), content);
+			}
 			Self::deposit_event(Event::<T>::PropertyReviewCreated(property_id, sender));
 
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn like_property_review(origin: OriginFor<T>, property_id: T::Hash) -> DispatchResult {
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 5))]
+		pub fn edit_property_review(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			rate: Rate,
+			review: WeakBoundedVec<u8, T::MaxReviewLength>,
+			content: Content,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let likes = PropertyReviewLikes::<T>::get(&property_id);
+			ensure!(rate <= MAX_RATE, Error::<T>::InvalidRate);
+			ensure!(content.is_valid(), Error::<T>::InvalidContent);
 
-			ensure!(!likes.contains(&sender), Error::<T>::PropertyReviewAlreadyLiked);
+			Self::ensure_not_migrating()?;
 
-			let mutate_result =
-				PropertyReviewLikes::<T>::try_mutate(&property_id, |x| x.try_push(sender.clone()));
+			let (old_rate, old_review, created) = Self::property_reviews(&property_id, &sender)
+				.ok_or(Error::<T>::PropertyReviewNotFound)?;
 
-			ensure!(mutate_result.is_ok(), Error::<T>::PropertyLikeLimitReached);
+			let key = (&property_id, &sender);
+			let mut edits = Self::property_review_edits(key);
 
-			let new_cnt = Self::property_reviews_likes_cnt(&property_id)
-				.checked_add(1)
-				.ok_or(Error::<T>::PropertyReviewsLikesOverflow)?;
+			edits
+				.try_push((<frame_system::Pallet<T>>::block_number(), old_rate, old_review))
+				.map_err(|_| Error::<T>::ReviewEditLimitReached)?;
+
+			// Hidden reviews stay out of the rating until they are restored.
+			let subject = ReviewSubject::Property(property_id);
 
-			PropertyReviewsLikesCnt::<T>::insert(&property_id, new_cnt);
-			Self::deposit_event(Event::<T>::PropertyReviewLikeCreated(property_id, sender));
+			if !Self::is_hidden(&subject, &sender) {
+				Self::exclude_from_rating(&subject, &sender, old_rate)?;
+				Self::include_in_rating(&subject, &sender, rate)?;
+			}
+			PropertyReviews::<T>::insert(&property_id, &sender, (rate, review, created));
+			PropertyReviewEdits::<T>::insert(key, edits);
+			if content.is_none() {
+				PropertyReviewContent::<T>::remove(key);
+			} else {
+				PropertyReviewContent::<T>::insert(key, content);
+			}
+			Self::deposit_event(Event::<T>::PropertyReviewUpdated(property_id, sender));
 
 			Ok(())
 		}
 
-		// -------------------- Pro
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn create_pro_review(
+		/// Withdraw the sender's review together with its likes, reply and edit history. `likes`
+		/// is at least the number of likes of the review and bounds the weight. The deposit stays
+		/// reserved until its challenge period is over, and a hidden review can only be removed
+		/// once its deposit was settled.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			10,
+			18 + *likes as u64 + T::MaxReportsPerReview::get() as u64,
+		))]
+		pub fn remove_property_review(
 			origin: OriginFor<T>,
-			pro_id: T::AccountId,
-			rate: Rate,
-			review: WeakBoundedVec<u8, T::MaxReviewLength>,
+			property_id: T::Hash,
+			likes: u32,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(rate <= MAX_RATE, Error::<T>::InvalidRate);
+			Self::ensure_not_migrating()?;
 
-			let mut reviews = Self::get_pro_reviews(&pro_id);
+			let (rate, _, _) = Self::property_reviews(&property_id, &sender)
+				.ok_or(Error::<T>::PropertyReviewNotFound)?;
+
+			let subject = ReviewSubject::Property(property_id);
 
 			ensure!(
-				reviews.clone().iter().find(|&x| x.0 == sender).is_none(),
-				Error::<T>::ProReviewAlreadyCreated
+				!PendingModeration::<T>::contains_key((&subject, &sender)),
+				Error::<T>::ReviewUnderModeration
+			);
+			ensure!(
+				!Self::is_hidden(&subject, &sender)
+					|| !ReviewDeposits::<T>::contains_key((&subject, &sender)),
+				Error::<T>::ReviewIsHidden
 			);
 
-			reviews.push((sender.clone(), rate, review));
+			let key = (&property_id, &sender);
 
-			let new_rate =
-				Self::add_to_rate(Self::pro_rate(&pro_id), rate, Self::pro_reviews_cnt(&pro_id));
-			let new_cnt = Self::pro_reviews_cnt(&pro_id)
+			ensure!(Self::property_review_like_cnt(key) <= likes, Error::<T>::LikeWitnessTooLow);
+
+			if !Self::is_hidden(&subject, &sender) {
+				Self::exclude_from_rating(&subject, &sender, rate)?;
+			}
+
+			if PropertyReplies::<T>::contains_key(key) {
+				let new_reply_cnt = Self::property_reply_cnt(&property_id)
+					.checked_sub(1)
+					.ok_or(Error::<T>::PropertyReplyOverflow)?;
+
+				PropertyReplies::<T>::remove(key);
+				PropertyReplyCnt::<T>::insert(&property_id, new_reply_cnt);
+			}
+
+			PropertyReviews::<T>::remove(&property_id, &sender);
+			ReviewsByReviewer::<T>::remove(&sender, &subject);
+			let _ = PropertyReviewLikers::<T>::clear_prefix(key, likes, None);
+			PropertyReviewLikeCnt::<T>::remove(key);
+			PropertyReviewEdits::<T>::remove(key);
+			PropertyReviewContent::<T>::remove(key);
+			VerifiedReviews::<T>::remove((&subject, &sender));
+			ReviewTips::<T>::remove((&subject, &sender));
+			Self::clear_moderation(&subject, &sender);
+			Self::deposit_event(Event::<T>::PropertyReviewRemoved(property_id, sender));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8, 4))]
+		pub fn like_property_review(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_not_migrating()?;
+			ensure!(sender != reviewer, Error::<T>::CannotLikeOwnReview);
+			ensure!(
+				Self::has_property_review(&property_id, &reviewer),
+				Error::<T>::PropertyReviewNotFound
+			);
+			ensure!(
+				!Self::is_hidden(&ReviewSubject::Property(property_id), &reviewer),
+				Error::<T>::ReviewIsHidden
+			);
+
+			let key = (&property_id, &reviewer);
+
+			ensure!(
+				!Self::property_review_likers(key, &sender),
+				Error::<T>::PropertyReviewAlreadyLiked
+			);
+
+			let new_cnt = Self::property_review_like_cnt(key)
 				.checked_add(1)
-				.ok_or(Error::<T>::ProReviewsOverflow)?;
+				.ok_or(Error::<T>::PropertyReviewsLikesOverflow)?;
 
-			ProReview::<T>::insert(&pro_id, reviews);
-			ProRate::<T>::insert(&pro_id, new_rate);
-			ProReviewsCnt::<T>::insert(&pro_id, new_cnt);
-			Self::deposit_event(Event::<T>::ProReviewCreated(pro_id, sender));
+			PropertyReviewLikers::<T>::insert(key, &sender, true);
+			PropertyReviewLikeCnt::<T>::insert(key, new_cnt);
+			Self::note_like(&ReviewSubject::Property(property_id), &reviewer, true);
+			Self::deposit_event(Event::<T>::PropertyReviewLikeCreated(
+				property_id,
+				reviewer,
+				sender,
+			));
 
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn like_pro_review(origin: OriginFor<T>, pro_id: T::AccountId) -> DispatchResult {
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 4))]
+		pub fn unlike_property_review(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let likes = ProReviewsLikes::<T>::get(&pro_id);
+			let key = (&property_id, &reviewer);
+
+			ensure!(Self::property_review_likers(key, &sender), Error::<T>::PropertyReviewNotLiked);
+
+			let new_cnt = Self::property_review_like_cnt(key)
+				.checked_sub(1)
+				.ok_or(Error::<T>::PropertyReviewsLikesOverflow)?;
+
+			PropertyReviewLikers::<T>::remove(key, &sender);
+			PropertyReviewLikeCnt::<T>::insert(key, new_cnt);
+			Self::note_like(&ReviewSubject::Property(property_id), &reviewer, false);
+			Self::deposit_event(Event::<T>::PropertyReviewLikeRemoved(
+				property_id,
+				reviewer,
+				sender,
+			));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn reply_to_property_review(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			reviewer: T::AccountId,
+			reply: WeakBoundedVec<u8, T::MaxReplyLength>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
 
-			ensure!(!likes.contains(&sender), Error::<T>::ProReviewAlreadyLiked);
+			Self::ensure_not_migrating()?;
+			ensure!(
+				T::PropertyOwnership::is_owner(&property_id, &sender),
+				Error::<T>::NotPropertyOwner
+			);
+			ensure!(
+				Self::has_property_review(&property_id, &reviewer),
+				Error::<T>::PropertyReviewNotFound
+			);
 
-			let mutate_result =
-				ProReviewsLikes::<T>::try_mutate(&pro_id, |x| x.try_push(sender.clone()));
+			let key = (&property_id, &reviewer);
 
-			ensure!(mutate_result.is_ok(), Error::<T>::ProLikeLimitReached);
+			ensure!(
+				!PropertyReplies::<T>::contains_key(key),
+				Error::<T>::PropertyReplyAlreadyCreated
+			);
 
-			let new_cnt = Self::pro_reviews_likes_cnt(&pro_id)
+			let new_cnt = Self::property_reply_cnt(&property_id)
 				.checked_add(1)
-				.ok_or(Error::<T>::ProReviewsLikesOverflow)?;
+				.ok_or(Error::<T>::PropertyReplyOverflow)?;
 
-			ProReviewsLikesCnt::<T>::insert(&pro_id, new_cnt);
-			Self::deposit_event(Event::<T>::ProReviewLikeCreated(pro_id, sender));
+			PropertyReplies::<T>::insert(key, (sender.clone(), reply));
+			PropertyReplyCnt::<T>::insert(&property_id, new_cnt);
+			Self::deposit_event(Event::<T>::PropertyReplyCreated(property_id, reviewer, sender));
 
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		fn get_property_reviews(
-			property_id: &T::Hash,
-		) -> Vec<Review<T::AccountId, T::MaxReviewLength>> {
-			PropertyReview::<T>::get(property_id).unwrap_or(vec![])
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn edit_property_reply(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			reviewer: T::AccountId,
+			reply: WeakBoundedVec<u8, T::MaxReplyLength>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				T::PropertyOwnership::is_owner(&property_id, &sender),
+				Error::<T>::NotPropertyOwner
+			);
+
+			let key = (&property_id, &reviewer);
+
+			ensure!(PropertyReplies::<T>::contains_key(key), Error::<T>::PropertyReplyNotFound);
+
+			PropertyReplies::<T>::insert(key, (sender.clone(), reply));
+			Self::deposit_event(Event::<T>::PropertyReplyUpdated(property_id, reviewer, sender));
+
+			Ok(())
 		}
 
-		fn get_pro_reviews(pro_id: &T::AccountId) -> Vec<Review<T::AccountId, T::MaxReviewLength>> {
-			ProReview::<T>::get(pro_id).unwrap_or(vec![])
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn remove_property_reply(
+			origin: OriginFor<T>,
+			property_id: T::Hash,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				T::PropertyOwnership::is_owner(&property_id, &sender),
+				Error::<T>::NotPropertyOwner
+			);
+
+			let key = (&property_id, &reviewer);
+
+			ensure!(PropertyReplies::<T>::contains_key(key), Error::<T>::PropertyReplyNotFound);
+
+			let new_cnt = Self::property_reply_cnt(&property_id)
+				.checked_sub(1)
+				.ok_or(Error::<T>::PropertyReplyOverflow)?;
+
+			PropertyReplies::<T>::remove(key);
+			PropertyReplyCnt::<T>::insert(&property_id, new_cnt);
+			Self::deposit_event(Event::<T>::PropertyReplyRemoved(property_id, reviewer, sender));
+
+			Ok(())
 		}
 
-		fn add_to_rate(current_rate: Rate, new_rate: Rate, total_rates: u32) -> Rate {
-			(((total_rates * current_rate as u32) + new_rate as u32) / (total_rates + 1_u32))
-				as Rate
+		// -------------------- Pro
+		/// `scores` holds one score per rating dimension of the pro's profession, or just the
+		/// overall rate if it has none. `review` is a short summary kept on chain for indexing,
+		/// `content` optionally points to the full body and media stored off chain.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(12, 12))]
+		pub fn create_pro_review(
+			origin: OriginFor<T>,
+			pro_id: T::AccountId,
+			scores: BoundedVec<Rate, T::MaxRatingDimensions>,
+			review: WeakBoundedVec<u8, T::MaxReviewLength>,
+			content: Content,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let rate = Self::overall_rate(&pro_id, &scores)?;
+			ensure!(content.is_valid(), Error::<T>::InvalidContent);
+
+			Self::ensure_not_migrating()?;
+			ensure!(sender != pro_id, Error::<T>::CannotReviewSelf);
+			ensure!(
+				T::ReviewEligibility::can_review_pro(&sender, &pro_id),
+				Error::<T>::NotEligibleToReview
+			);
+
+			ensure!(
+				!ProReviews::<T>::contains_key(&pro_id, &sender),
+				Error::<T>::ProReviewAlreadyCreated
+			);
+
+			let mut rating = Self::pro_rating(&pro_id);
+			rating.add(rate).ok_or(Error::<T>::RatingOverflow)?;
+			let new_cnt = Self::pro_reviews_cnt(&pro_id)
+				.checked_add(1)
+				.ok_or(Error::<T>::ProReviewsOverflow)?;
+
+			let subject = ReviewSubject::Pro(pro_id.clone());
+
+			Self::note_new_review(&subject, &sender)?;
+			Self::reserve_deposit(&subject, &sender)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			ProReviews::<T>::insert(&pro_id, &sender, (rate, review, now));
+			ReviewsByReviewer::<T>::insert(&sender, &subject, now);
+			Self::put_pro_rating(&pro_id, rating);
+			ProReviewsCnt::<T>::insert(&pro_id, new_cnt);
+			if !content.is_none() {
+				ProReviewContent::<T>::insert((&pro_id, &sender), content);
+			}
+			Self::store_scores(&pro_id, &sender, scores);
+			Self::update_dimension_ratings(&pro_id, &sender, true)?;
+			Self::deposit_event(Event::<T>::ProReviewCreated(pro_id, sender));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8, 8))]
+		pub fn edit_pro_review(
+			origin: OriginFor<T>,
+			pro_id: T::AccountId,
+			scores: BoundedVec<Rate, T::MaxRatingDimensions>,
+			review: WeakBoundedVec<u8, T::MaxReviewLength>,
+			content: Content,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let rate = Self::overall_rate(&pro_id, &scores)?;
+			ensure!(content.is_valid(), Error::<T>::InvalidContent);
+
+			Self::ensure_not_migrating()?;
+
+			let (old_rate, old_review, created) =
+				Self::pro_reviews(&pro_id, &sender).ok_or(Error::<T>::ProReviewNotFound)?;
+
+			let key = (&pro_id, &sender);
+			let mut edits = Self::pro_review_edits(key);
+
+			edits
+				.try_push((<frame_system::Pallet<T>>::block_number(), old_rate, old_review))
+				.map_err(|_| Error::<T>::ReviewEditLimitReached)?;
+
+			// Hidden reviews stay out of the rating until they are restored.
+			let subject = ReviewSubject::Pro(pro_id.clone());
+			let hidden = Self::is_hidden(&subject, &sender);
+
+			if !hidden {
+				Self::exclude_from_rating(&subject, &sender, old_rate)?;
+			}
+			Self::store_scores(&pro_id, &sender, scores);
+			if !hidden {
+				Self::include_in_rating(&subject, &sender, rate)?;
+			}
+			ProReviews::<T>::insert(&pro_id, &sender, (rate, review, created));
+			ProReviewEdits::<T>::insert(key, edits);
+			if content.is_none() {
+				ProReviewContent::<T>::remove(key);
+			} else {
+				ProReviewContent::<T>::insert(key, content);
+			}
+			Self::deposit_event(Event::<T>::ProReviewUpdated(pro_id, sender));
+
+			Ok(())
+		}
+
+		/// Withdraw the sender's review together with its likes, reply and edit history. `likes`
+		/// is at least the number of likes of the review and bounds the weight. The deposit stays
+		/// reserved until its challenge period is over, and a hidden review can only be removed
+		/// once its deposit was settled.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			10,
+			19 + *likes as u64 + T::MaxReportsPerReview::get() as u64,
+		))]
+		pub fn remove_pro_review(
+			origin: OriginFor<T>,
+			pro_id: T::AccountId,
+			likes: u32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_not_migrating()?;
+
+			let (rate, _, _) =
+				Self::pro_reviews(&pro_id, &sender).ok_or(Error::<T>::ProReviewNotFound)?;
+
+			let subject = ReviewSubject::Pro(pro_id.clone());
+
+			ensure!(
+				!PendingModeration::<T>::contains_key((&subject, &sender)),
+				Error::<T>::ReviewUnderModeration
+			);
+			ensure!(
+				!Self::is_hidden(&subject, &sender)
+					|| !ReviewDeposits::<T>::contains_key((&subject, &sender)),
+				Error::<T>::ReviewIsHidden
+			);
+
+			let key = (&pro_id, &sender);
+
+			ensure!(Self::pro_review_like_cnt(key) <= likes, Error::<T>::LikeWitnessTooLow);
+
+			if !Self::is_hidden(&subject, &sender) {
+				Self::exclude_from_rating(&subject, &sender, rate)?;
+			}
+
+			if ProReplies::<T>::contains_key(key) {
+				let new_reply_cnt = Self::pro_reply_cnt(&pro_id)
+					.checked_sub(1)
+					.ok_or(Error::<T>::ProReplyOverflow)?;
+
+				ProReplies::<T>::remove(key);
+				ProReplyCnt::<T>::insert(&pro_id, new_reply_cnt);
+			}
+
+			ProReviews::<T>::remove(&pro_id, &sender);
+			ReviewsByReviewer::<T>::remove(&sender, &subject);
+			ProReviewScores::<T>::remove(&pro_id, &sender);
+			let _ = ProReviewLikers::<T>::clear_prefix(key, likes, None);
+			ProReviewLikeCnt::<T>::remove(key);
+			ProReviewEdits::<T>::remove(key);
+			ProReviewContent::<T>::remove(key);
+			VerifiedReviews::<T>::remove((&subject, &sender));
+			ReviewTips::<T>::remove((&subject, &sender));
+			Self::clear_moderation(&subject, &sender);
+			Self::deposit_event(Event::<T>::ProReviewRemoved(pro_id, sender));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8, 4))]
+		pub fn like_pro_review(
+			origin: OriginFor<T>,
+			pro_id: T::AccountId,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_not_migrating()?;
+			ensure!(sender != reviewer, Error::<T>::CannotLikeOwnReview);
+			ensure!(Self::has_pro_review(&pro_id, &reviewer), Error::<T>::ProReviewNotFound);
+			ensure!(
+				!Self::is_hidden(&ReviewSubject::Pro(pro_id.clone()), &reviewer),
+				Error::<T>::ReviewIsHidden
+			);
+
+			let key = (&pro_id, &reviewer);
+
+			ensure!(!Self::pro_review_likers(key, &sender), Error::<T>::ProReviewAlreadyLiked);
+
+			let new_cnt = Self::pro_review_like_cnt(key)
+				.checked_add(1)
+				.ok_or(Error::<T>::ProReviewsLikesOverflow)?;
+
+			ProReviewLikers::<T>::insert(key, &sender, true);
+			ProReviewLikeCnt::<T>::insert(key, new_cnt);
+			Self::note_like(&ReviewSubject::Pro(pro_id.clone()), &reviewer, true);
+			Self::deposit_event(Event::<T>::ProReviewLikeCreated(pro_id, reviewer, sender));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 4))]
+		pub fn unlike_pro_review(
+			origin: OriginFor<T>,
+			pro_id: T::AccountId,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let key = (&pro_id, &reviewer);
+
+			ensure!(Self::pro_review_likers(key, &sender), Error::<T>::ProReviewNotLiked);
+
+			let new_cnt = Self::pro_review_like_cnt(key)
+				.checked_sub(1)
+				.ok_or(Error::<T>::ProReviewsLikesOverflow)?;
+
+			ProReviewLikers::<T>::remove(key, &sender);
+			ProReviewLikeCnt::<T>::insert(key, new_cnt);
+			Self::note_like(&ReviewSubject::Pro(pro_id.clone()), &reviewer, false);
+			Self::deposit_event(Event::<T>::ProReviewLikeRemoved(pro_id, reviewer, sender));
+
+			Ok(())
+		}
+
+		/// The sender is the pro the review is about.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn reply_to_pro_review(
+			origin: OriginFor<T>,
+			reviewer: T::AccountId,
+			reply: WeakBoundedVec<u8, T::MaxReplyLength>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_not_migrating()?;
+			ensure!(Self::has_pro_review(&sender, &reviewer), Error::<T>::ProReviewNotFound);
+
+			let key = (&sender, &reviewer);
+
+			ensure!(!ProReplies::<T>::contains_key(key), Error::<T>::ProReplyAlreadyCreated);
+
+			let new_cnt = Self::pro_reply_cnt(&sender)
+				.checked_add(1)
+				.ok_or(Error::<T>::ProReplyOverflow)?;
+
+			ProReplies::<T>::insert(key, (sender.clone(), reply));
+			ProReplyCnt::<T>::insert(&sender, new_cnt);
+			Self::deposit_event(Event::<T>::ProReplyCreated(sender.clone(), reviewer, sender));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn edit_pro_reply(
+			origin: OriginFor<T>,
+			reviewer: T::AccountId,
+			reply: WeakBoundedVec<u8, T::MaxReplyLength>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let key = (&sender, &reviewer);
+
+			ensure!(ProReplies::<T>::contains_key(key), Error::<T>::ProReplyNotFound);
+
+			ProReplies::<T>::insert(key, (sender.clone(), reply));
+			Self::deposit_event(Event::<T>::ProReplyUpdated(sender, reviewer));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn remove_pro_reply(origin: OriginFor<T>, reviewer: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let key = (&sender, &reviewer);
+
+			ensure!(ProReplies::<T>::contains_key(key), Error::<T>::ProReplyNotFound);
+
+			let new_cnt = Self::pro_reply_cnt(&sender)
+				.checked_sub(1)
+				.ok_or(Error::<T>::ProReplyOverflow)?;
+
+			ProReplies::<T>::remove(key);
+			ProReplyCnt::<T>::insert(&sender, new_cnt);
+			Self::deposit_event(Event::<T>::ProReplyRemoved(sender, reviewer));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_rating_dimensions(
+			origin: OriginFor<T>,
+			profession: T::Profession,
+			dimensions: BoundedVec<
+				RatingDimension<T::MaxDimensionNameLength>,
+				T::MaxRatingDimensions,
+			>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(
+				dimensions.is_empty() || dimensions.iter().any(|x| x.1 > 0),
+				Error::<T>::InvalidDimensions
+			);
+
+			RatingDimensions::<T>::insert(&profession, dimensions);
+			Self::deposit_event(Event::<T>::RatingDimensionsSet(profession));
+
+			Ok(())
+		}
+
+		// -------------------- Verification
+		/// Backs the sender's review of `subject` with proof that the engagement took place. Only
+		/// the weighted pro rating takes it into account.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn verify_review(
+			origin: OriginFor<T>,
+			subject: ReviewSubject<T::AccountId, T::Hash>,
+			proof: T::EngagementProof,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::review_rate(&subject, &sender).is_some(), Error::<T>::ReviewNotFound);
+
+			let key = (&subject, &sender);
+
+			ensure!(!VerifiedReviews::<T>::contains_key(key), Error::<T>::ReviewAlreadyVerified);
+			ensure!(
+				T::EngagementVerifier::verify(&sender, &subject, &proof),
+				Error::<T>::InvalidEngagementProof
+			);
+
+			VerifiedReviews::<T>::insert(key, <frame_system::Pallet<T>>::block_number());
+			Self::deposit_event(Event::<T>::ReviewVerified(subject, sender));
+
+			Ok(())
+		}
+
+		// -------------------- Tips
+		/// Sends `amount` to a pro or to the author of a review as thanks.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 4))]
+		pub fn tip(
+			origin: OriginFor<T>,
+			target: TipTarget<T::AccountId, T::Hash>,
+			amount: BalanceOf<T>,
+			memo: Option<BoundedVec<u8, T::MaxTipMemoLength>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroTip);
+
+			let recipient = match &target {
+				TipTarget::Pro(pro_id) => {
+					ensure!(T::ProProfession::profession(pro_id).is_some(), Error::<T>::NotAPro);
+					pro_id.clone()
+				},
+				TipTarget::Review(subject, reviewer) => {
+					ensure!(
+						Self::review_rate(subject, reviewer).is_some(),
+						Error::<T>::ReviewNotFound
+					);
+					ensure!(!Self::is_hidden(subject, reviewer), Error::<T>::ReviewIsHidden);
+					reviewer.clone()
+				},
+			};
+
+			ensure!(sender != recipient, Error::<T>::CannotTipSelf);
+
+			T::Currency::transfer(&sender, &recipient, amount, ExistenceRequirement::KeepAlive)?;
+
+			TipsReceived::<T>::mutate(&recipient, |x| *x = x.saturating_add(amount));
+			if let TipTarget::Review(subject, reviewer) = &target {
+				ReviewTips::<T>::mutate((subject, reviewer), |x| *x = x.saturating_add(amount));
+			}
+			Self::deposit_event(Event::<T>::Tipped(sender, target, amount, memo));
+
+			Ok(())
+		}
+
+		// -------------------- Moderation
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 3))]
+		pub fn report_review(
+			origin: OriginFor<T>,
+			subject: ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: T::AccountId,
+			reason: ReportReason,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(sender != reviewer, Error::<T>::CannotReportOwnReview);
+			ensure!(Self::review_rate(&subject, &reviewer).is_some(), Error::<T>::ReviewNotFound);
+
+			let key = (&subject, &reviewer);
+
+			ensure!(!Reports::<T>::contains_key(key, &sender), Error::<T>::ReviewAlreadyReported);
+
+			let new_cnt =
+				Self::report_cnt(key).checked_add(1).ok_or(Error::<T>::ReportsOverflow)?;
+			ensure!(new_cnt <= T::MaxReportsPerReview::get(), Error::<T>::ReportLimitReached);
+
+			Reports::<T>::insert(key, &sender, reason);
+			ReportCnt::<T>::insert(key, new_cnt);
+			Self::deposit_event(Event::<T>::ReviewReported(
+				subject.clone(),
+				reviewer.clone(),
+				sender,
+				reason,
+			));
+
+			if new_cnt == T::ReportThreshold::get() && !HiddenReviews::<T>::contains_key(key) {
+				PendingModeration::<T>::insert(key, <frame_system::Pallet<T>>::block_number());
+				Self::deposit_event(Event::<T>::ReviewEscalated(subject, reviewer));
+			}
+
+			Ok(())
+		}
+
+		/// Takes a review out of its subject's rating. Its deposit is held for a new challenge
+		/// period, and slashed once that is over unless the review was restored in the meantime.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7, 9))]
+		pub fn hide_review(
+			origin: OriginFor<T>,
+			subject: ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
+			T::ModerationOrigin::ensure_origin(origin)?;
+
+			let rate = Self::review_rate(&subject, &reviewer).ok_or(Error::<T>::ReviewNotFound)?;
+			let key = (&subject, &reviewer);
+
+			ensure!(!HiddenReviews::<T>::contains_key(key), Error::<T>::ReviewAlreadyHidden);
+
+			Self::exclude_from_rating(&subject, &reviewer, rate)?;
+			Self::hold_deposit(&subject, &reviewer);
+
+			HiddenReviews::<T>::insert(key, <frame_system::Pallet<T>>::block_number());
+			PendingModeration::<T>::remove(key);
+			Self::log_moderation(&subject, &reviewer, ModerationAction::Hidden)?;
+			Self::deposit_event(Event::<T>::ReviewHidden(subject, reviewer));
+
+			Ok(())
+		}
+
+		/// Puts a hidden review back into its subject's rating. Its reports are dropped so it can
+		/// be reported again, and its deposit is returned once the challenge period is over.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			5,
+			9 + T::MaxReportsPerReview::get() as u64,
+		))]
+		pub fn restore_review(
+			origin: OriginFor<T>,
+			subject: ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
+			T::ModerationOrigin::ensure_origin(origin)?;
+
+			let rate = Self::review_rate(&subject, &reviewer).ok_or(Error::<T>::ReviewNotFound)?;
+
+			ensure!(Self::is_hidden(&subject, &reviewer), Error::<T>::ReviewNotHidden);
+
+			Self::include_in_rating(&subject, &reviewer, rate)?;
+
+			Self::clear_moderation(&subject, &reviewer);
+			Self::log_moderation(&subject, &reviewer, ModerationAction::Restored)?;
+			Self::deposit_event(Event::<T>::ReviewRestored(subject, reviewer));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			2,
+			5 + T::MaxReportsPerReview::get() as u64,
+		))]
+		pub fn dismiss_reports(
+			origin: OriginFor<T>,
+			subject: ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
+			T::ModerationOrigin::ensure_origin(origin)?;
+
+			let key = (&subject, &reviewer);
+
+			ensure!(ReportCnt::<T>::contains_key(key), Error::<T>::NoReports);
+
+			let _ = Reports::<T>::clear_prefix(key, T::MaxReportsPerReview::get(), None);
+			ReportCnt::<T>::remove(key);
+			PendingModeration::<T>::remove(key);
+			Self::log_moderation(&subject, &reviewer, ModerationAction::ReportsDismissed)?;
+			Self::deposit_event(Event::<T>::ReportsDismissed(subject, reviewer));
+
+			Ok(())
+		}
+
+		/// Lets a subject take new reviews again before its surge lock runs out.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn lift_surge_lock(
+			origin: OriginFor<T>,
+			subject: ReviewSubject<T::AccountId, T::Hash>,
+		) -> DispatchResult {
+			T::ModerationOrigin::ensure_origin(origin)?;
+
+			ensure!(SurgeLocks::<T>::contains_key(&subject), Error::<T>::SubjectNotLocked);
+
+			SurgeLocks::<T>::remove(&subject);
+			Self::deposit_event(Event::<T>::ReviewSurgeLockLifted(subject));
+
+			Ok(())
+		}
+
+		/// Settles a review deposit once its challenge period is over: it is returned, or slashed
+		/// if the review is still hidden. Anyone can trigger it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 2))]
+		pub fn release_deposit(
+			origin: OriginFor<T>,
+			subject: ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: T::AccountId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let key = (&subject, &reviewer);
+			let (_, release_at) = Self::review_deposits(key).ok_or(Error::<T>::DepositNotFound)?;
+
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= release_at,
+				Error::<T>::ChallengePeriodNotOver
+			);
+			ensure!(!PendingModeration::<T>::contains_key(key), Error::<T>::ReviewUnderModeration);
+
+			if Self::is_hidden(&subject, &reviewer) {
+				Self::slash_deposit(&subject, &reviewer);
+			} else {
+				Self::unreserve_deposit(&subject, &reviewer);
+			}
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn ensure_not_migrating() -> DispatchResult {
+			ensure!(!Self::migration_pending(), Error::<T>::MigrationInProgress);
+			Ok(())
+		}
+
+		fn review_rate(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+		) -> Option<Rate> {
+			match subject {
+				ReviewSubject::Pro(pro_id) => Self::pro_reviews(pro_id, reviewer).map(|x| x.0),
+				ReviewSubject::Property(property_id) => {
+					Self::property_reviews(property_id, reviewer).map(|x| x.0)
+				},
+			}
+		}
+
+		pub fn is_verified(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+		) -> bool {
+			VerifiedReviews::<T>::contains_key((subject, reviewer))
+		}
+
+		pub fn is_hidden(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+		) -> bool {
+			HiddenReviews::<T>::contains_key((subject, reviewer))
+		}
+
+		fn include_in_rating(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+			rate: Rate,
+		) -> DispatchResult {
+			match subject {
+				ReviewSubject::Pro(pro_id) => {
+					let mut rating = Self::pro_rating(pro_id);
+					rating.add(rate).ok_or(Error::<T>::RatingOverflow)?;
+					let new_cnt = Self::pro_reviews_cnt(pro_id)
+						.checked_add(1)
+						.ok_or(Error::<T>::ProReviewsOverflow)?;
+
+					Self::put_pro_rating(pro_id, rating);
+					ProReviewsCnt::<T>::insert(pro_id, new_cnt);
+					Self::update_dimension_ratings(pro_id, reviewer, true)?;
+				},
+				ReviewSubject::Property(property_id) => {
+					let mut rating = Self::property_rating(property_id);
+					rating.add(rate).ok_or(Error::<T>::RatingOverflow)?;
+					let new_cnt = Self::property_reviews_cnt(property_id)
+						.checked_add(1)
+						.ok_or(Error::<T>::PropertyReviewsOverflow)?;
+
+					PropertyRating::<T>::insert(property_id, rating);
+					PropertyReviewsCnt::<T>::insert(property_id, new_cnt);
+				},
+			}
+
+			Ok(())
+		}
+
+		fn exclude_from_rating(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+			rate: Rate,
+		) -> DispatchResult {
+			match subject {
+				ReviewSubject::Pro(pro_id) => {
+					let mut rating = Self::pro_rating(pro_id);
+					rating.remove(rate).ok_or(Error::<T>::RatingOverflow)?;
+					let new_cnt = Self::pro_reviews_cnt(pro_id)
+						.checked_sub(1)
+						.ok_or(Error::<T>::ProReviewsOverflow)?;
+
+					Self::put_pro_rating(pro_id, rating);
+					ProReviewsCnt::<T>::insert(pro_id, new_cnt);
+					Self::update_dimension_ratings(pro_id, reviewer, false)?;
+				},
+				ReviewSubject::Property(property_id) => {
+					let mut rating = Self::property_rating(property_id);
+					rating.remove(rate).ok_or(Error::<T>::RatingOverflow)?;
+					let new_cnt = Self::property_reviews_cnt(property_id)
+						.checked_sub(1)
+						.ok_or(Error::<T>::PropertyReviewsOverflow)?;
+
+					PropertyRating::<T>::insert(property_id, rating);
+					PropertyReviewsCnt::<T>::insert(property_id, new_cnt);
+				},
+			}
+
+			Ok(())
+		}
+
+		pub(crate) fn put_pro_rating(pro_id: &T::AccountId, rating: Rating) {
+			ProRate::<T>::insert(pro_id, rating.rate());
+			ProRating::<T>::insert(pro_id, rating);
+		}
+
+		pub(crate) fn dimensions_of(
+			pro_id: &T::AccountId,
+		) -> BoundedVec<RatingDimension<T::MaxDimensionNameLength>, T::MaxRatingDimensions> {
+			T::ProProfession::profession(pro_id)
+				.map(Self::rating_dimensions)
+				.unwrap_or_default()
+		}
+
+		/// Weighted average of `scores`, rounded to the nearest rate. Without dimensions the
+		/// single score is the rate itself.
+		fn overall_rate(pro_id: &T::AccountId, scores: &[Rate]) -> Result<Rate, DispatchError> {
+			ensure!(scores.iter().all(|x| *x <= MAX_RATE), Error::<T>::InvalidRate);
+
+			let dimensions = Self::dimensions_of(pro_id);
+
+			if dimensions.is_empty() {
+				ensure!(scores.len() == 1, Error::<T>::InvalidScores);
+				return Ok(scores[0]);
+			}
+
+			ensure!(scores.len() == dimensions.len(), Error::<T>::InvalidScores);
+
+			let total: u32 = dimensions.iter().map(|x| x.1 as u32).sum();
+			let weighted: u32 =
+				scores.iter().zip(dimensions.iter()).map(|(s, d)| *s as u32 * d.1 as u32).sum();
+
+			Ok(((weighted + total / 2) / total) as Rate)
+		}
+
+		/// Keeps the per dimension scores of a review, if its pro is scored on dimensions.
+		fn store_scores(
+			pro_id: &T::AccountId,
+			reviewer: &T::AccountId,
+			scores: BoundedVec<Rate, T::MaxRatingDimensions>,
+		) {
+			if Self::dimensions_of(pro_id).is_empty() {
+				ProReviewScores::<T>::remove(pro_id, reviewer);
+			} else {
+				ProReviewScores::<T>::insert(pro_id, reviewer, scores);
+			}
+		}
+
+		fn update_dimension_ratings(
+			pro_id: &T::AccountId,
+			reviewer: &T::AccountId,
+			add: bool,
+		) -> DispatchResult {
+			if let Some(scores) = Self::pro_review_scores(pro_id, reviewer) {
+				for (i, score) in scores.into_iter().enumerate() {
+					let mut rating = Self::pro_dimension_rating(pro_id, i as u32);
+					if add {
+						rating.add(score).ok_or(Error::<T>::RatingOverflow)?;
+					} else {
+						rating.remove(score).ok_or(Error::<T>::RatingOverflow)?;
+					}
+					ProDimensionRating::<T>::insert(pro_id, i as u32, rating);
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Counts a new review against the reviewer's limit and the subject's surge window. The
+		/// review that reaches the surge threshold still goes through, the ones after it do not.
+		fn note_new_review(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+		) -> DispatchResult {
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			if let Some(until) = Self::surge_locks(subject) {
+				ensure!(now >= until, Error::<T>::SubjectLocked);
+				SurgeLocks::<T>::remove(subject);
+			}
+
+			let (mut period_end, mut written) = Self::reviewer_activity(reviewer);
+			if period_end <= now {
+				period_end = now.saturating_add(T::ReviewPeriod::get());
+				written = 0;
+			}
+
+			ensure!(written < T::MaxReviewsPerPeriod::get(), Error::<T>::ReviewLimitReached);
+			ReviewerActivity::<T>::insert(reviewer, (period_end, written + 1));
+
+			let (mut window_end, mut received) = Self::subject_activity(subject);
+			if window_end <= now {
+				window_end = now.saturating_add(T::SurgeWindow::get());
+				received = 0;
+			}
+			received = received.saturating_add(1);
+
+			if received >= T::SurgeThreshold::get() {
+				let until = now.saturating_add(T::SurgeLockPeriod::get());
+
+				SurgeLocks::<T>::insert(subject, until);
+				SubjectActivity::<T>::remove(subject);
+				Self::deposit_event(Event::<T>::ReviewSurgeLocked(subject.clone(), until));
+			} else {
+				SubjectActivity::<T>::insert(subject, (window_end, received));
+			}
+
+			Ok(())
+		}
+
+		pub fn reward_pool_account() -> T::AccountId {
+			T::RewardPalletId::get().into_account_truncating()
+		}
+
+		/// Counts a like towards the current reward period. An unlike takes back a like of the
+		/// current period, if there is one.
+		fn note_like(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+			liked: bool,
+		) {
+			let period = Self::reward_period();
+			let threshold = T::RewardLikeThreshold::get().max(1);
+			let rewarded = |likes: u32| if likes >= threshold { likes } else { 0 };
+
+			let before = Self::period_likes(period, (subject, reviewer));
+			let after = if liked { before.saturating_add(1) } else { before.saturating_sub(1) };
+
+			PeriodLikes::<T>::insert(period, (subject, reviewer), after);
+			PeriodRewardedLikes::<T>::mutate(period, |x| {
+				*x = x.saturating_sub(rewarded(before)).saturating_add(rewarded(after))
+			});
+		}
+
+		/// Freezes the pool balance the ending period's reviewers share and starts the next period.
+		fn close_reward_period(now: T::BlockNumber) -> Weight {
+			let period = Self::reward_period();
+			let pool = Self::reward_pool_account();
+			let pot =
+				T::Currency::free_balance(&pool).saturating_sub(T::Currency::minimum_balance());
+
+			PendingPayout::<T>::put((period, pot, Self::period_rewarded_likes(period)));
+			PeriodRewardedLikes::<T>::remove(period);
+			RewardPeriodIndex::<T>::put(period.wrapping_add(1));
+			NextRewardAt::<T>::put(now.saturating_add(T::RewardPeriod::get()));
+			Self::deposit_event(Event::<T>::RewardPeriodClosed(period, pot));
+
+			T::DbWeight::get().reads_writes(3, 4)
+		}
+
+		/// Settles up to `MaxPayoutsPerBlock` reviews of the period being paid out. Reviews that
+		/// were removed or hidden in the meantime get nothing and their share stays in the pool.
+		fn pay_rewards((period, pot, total): (u32, BalanceOf<T>, u32)) -> Weight {
+			let pool = Self::reward_pool_account();
+			let threshold = T::RewardLikeThreshold::get().max(1);
+			let limit = T::MaxPayoutsPerBlock::get();
+			let mut settled: u32 = 0;
+			let mut paid: u64 = 0;
+
+			for ((subject, reviewer), likes) in
+				PeriodLikes::<T>::drain_prefix(period).take(limit as usize)
+			{
+				settled += 1;
+
+				if likes < threshold
+					|| Self::review_rate(&subject, &reviewer).is_none()
+					|| Self::is_hidden(&subject, &reviewer)
+				{
+					continue;
+				}
+
+				let amount = Perbill::from_rational(likes, total) * pot;
+
+				if amount.is_zero() {
+					continue;
+				}
+
+				if T::Currency::transfer(&pool, &reviewer, amount, ExistenceRequirement::KeepAlive)
+					.is_ok()
+				{
+					paid += 1;
+					Self::deposit_event(Event::<T>::ReviewRewardPaid(subject, reviewer, amount));
+				}
+			}
+
+			if settled < limit {
+				PendingPayout::<T>::kill();
+			}
+
+			T::DbWeight::get()
+				.reads_writes(u64::from(settled) * 3 + 1, u64::from(settled) + paid * 2 + 1)
+		}
+
+		fn reserve_deposit(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+		) -> DispatchResult {
+			let amount = T::ReviewDeposit::get();
+			let release_at = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::DepositChallengePeriod::get());
+
+			T::Currency::reserve(reviewer, amount)?;
+			// The deposit of a removed review may still be held, in which case both are settled
+			// together.
+			ReviewDeposits::<T>::mutate((subject, reviewer), |x| {
+				let held = x.as_ref().map(|x| x.0).unwrap_or_else(Zero::zero);
+				*x = Some((held.saturating_add(amount), release_at));
+			});
+
+			Ok(())
+		}
+
+		/// Restarts the challenge period of a review deposit.
+		fn hold_deposit(subject: &ReviewSubject<T::AccountId, T::Hash>, reviewer: &T::AccountId) {
+			let release_at = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::DepositChallengePeriod::get());
+
+			ReviewDeposits::<T>::mutate((subject, reviewer), |x| {
+				if let Some((_, at)) = x {
+					*at = release_at.max(*at);
+				}
+			});
+		}
+
+		fn unreserve_deposit(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+		) {
+			if let Some((amount, _)) = ReviewDeposits::<T>::take((subject, reviewer)) {
+				T::Currency::unreserve(reviewer, amount);
+				Self::deposit_event(Event::<T>::DepositReleased(
+					subject.clone(),
+					reviewer.clone(),
+					amount,
+				));
+			}
+		}
+
+		fn slash_deposit(subject: &ReviewSubject<T::AccountId, T::Hash>, reviewer: &T::AccountId) {
+			if let Some((amount, _)) = ReviewDeposits::<T>::take((subject, reviewer)) {
+				let (imbalance, _) = T::Currency::slash_reserved(reviewer, amount);
+				T::Slashed::on_unbalanced(imbalance);
+				Self::deposit_event(Event::<T>::DepositSlashed(
+					subject.clone(),
+					reviewer.clone(),
+					amount,
+				));
+			}
+		}
+
+		/// Drops every report and moderation flag of a review.
+		fn clear_moderation(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+		) {
+			let key = (subject, reviewer);
+
+			let _ = Reports::<T>::clear_prefix(key, T::MaxReportsPerReview::get(), None);
+			ReportCnt::<T>::remove(key);
+			PendingModeration::<T>::remove(key);
+			HiddenReviews::<T>::remove(key);
+		}
+
+		fn log_moderation(
+			subject: &ReviewSubject<T::AccountId, T::Hash>,
+			reviewer: &T::AccountId,
+			action: ModerationAction,
+		) -> DispatchResult {
+			let id = Self::moderation_log_cnt();
+			let new_cnt = id.checked_add(1).ok_or(Error::<T>::ModerationLogOverflow)?;
+
+			ModerationLog::<T>::insert(
+				id,
+				ModerationRecord {
+					subject: subject.clone(),
+					reviewer: reviewer.clone(),
+					action,
+					block: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+			ModerationLogCnt::<T>::put(new_cnt);
+
+			Ok(())
+		}
+
+		fn has_property_review(property_id: &T::Hash, reviewer: &T::AccountId) -> bool {
+			PropertyReviews::<T>::contains_key(property_id, reviewer)
+		}
+
+		fn has_pro_review(pro_id: &T::AccountId, reviewer: &T::AccountId) -> bool {
+			ProReviews::<T>::contains_key(pro_id, reviewer)
+		}
+
+		/// Average rate of `pro_id` where every review is weighted by the standing of its reviewer
+		/// and decays with its age.
+		pub fn weighted_pro_rating(pro_id: &T::AccountId) -> FixedU128 {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut total = FixedU128::zero();
+			let mut weighted = FixedU128::zero();
+
+			for (reviewer, (rate, _, created)) in ProReviews::<T>::iter_prefix(pro_id) {
+				let subject = ReviewSubject::Pro(pro_id.clone());
+
+				if Self::is_hidden(&subject, &reviewer) {
+					continue;
+				}
+
+				let written = Self::pro_review_edited_at(pro_id, &reviewer).unwrap_or(created);
+				let mut weight = Self::reviewer_weight(pro_id, &reviewer, now)
+					.saturating_mul(Self::decay(now.saturating_sub(written)));
+
+				if Self::is_verified(&subject, &reviewer) {
+					weight =
+						weight.saturating_mul(FixedU128::saturating_from_integer(VERIFIED_WEIGHT));
+				}
+
+				total = total.saturating_add(weight);
+				weighted = weighted.saturating_add(
+					weight.saturating_mul(FixedU128::saturating_from_integer(rate)),
+				);
+			}
+
+			weighted.checked_div(&total).unwrap_or_else(FixedU128::zero)
+		}
+
+		/// From 1/2 for a newcomer up to 4 for a mature, reputable reviewer connected to the pro.
+		fn reviewer_weight(
+			pro_id: &T::AccountId,
+			reviewer: &T::AccountId,
+			now: T::BlockNumber,
+		) -> FixedU128 {
+			let maturity = T::ReviewerMaturity::get();
+			let age = T::ReviewerStanding::member_since(reviewer)
+				.map(|x| now.saturating_sub(x))
+				.unwrap_or_else(Zero::zero);
+			let half = FixedU128::saturating_from_rational(1u32, 2u32);
+
+			let age_factor = if maturity.is_zero() {
+				FixedU128::one()
+			} else {
+				half.saturating_add(half.saturating_mul(FixedU128::saturating_from_rational(
+					age.min(maturity).saturated_into::<u128>(),
+					maturity.saturated_into::<u128>(),
+				)))
+			};
+			let reputation_factor =
+				FixedU128::one().saturating_add(FixedU128::saturating_from_rational(
+					T::ReviewerStanding::reputation(reviewer).min(FULL_REPUTATION),
+					FULL_REPUTATION,
+				));
+			let connection_factor = if T::ReviewerStanding::is_connected(reviewer, pro_id) {
+				FixedU128::saturating_from_integer(2u32)
+			} else {
+				FixedU128::one()
+			};
+
+			age_factor.saturating_mul(reputation_factor).saturating_mul(connection_factor)
+		}
+
+		/// Halves every `RatingHalfLife`, interpolated linearly in between.
+		fn decay(elapsed: T::BlockNumber) -> FixedU128 {
+			let half_life = T::RatingHalfLife::get();
+
+			if half_life.is_zero() {
+				return FixedU128::one();
+			}
+
+			let halvings = (elapsed / half_life).saturated_into::<u32>();
+			let rest = elapsed % half_life;
+
+			if halvings >= 64 {
+				return FixedU128::zero();
+			}
+
+			FixedU128::saturating_from_rational(1u128, 1u128 << halvings).saturating_mul(
+				FixedU128::one().saturating_sub(FixedU128::saturating_from_rational(
+					rest.saturated_into::<u128>(),
+					half_life.saturated_into::<u128>().saturating_mul(2),
+				)),
+			)
+		}
+
+		/// Block the current version of a review was written at, if it has been edited.
+		pub fn pro_review_edited_at(
+			pro_id: &T::AccountId,
+			reviewer: &T::AccountId,
+		) -> Option<T::BlockNumber> {
+			Self::pro_review_edits((pro_id, reviewer)).last().map(|x| x.0)
+		}
+
+		/// Block the current version of a review was written at, if it has been edited.
+		pub fn property_review_edited_at(
+			property_id: &T::Hash,
+			reviewer: &T::AccountId,
+		) -> Option<T::BlockNumber> {
+			Self::property_review_edits((property_id, reviewer)).last().map(|x| x.0)
 		}
 	}
 }
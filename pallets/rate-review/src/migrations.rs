@@ -0,0 +1,199 @@
+use crate::{
+	Config, MigrationOffset, MigrationPending, NextRewardAt, Pallet, ProReview, ProReviews,
+	PropertyRating, PropertyReview, PropertyReviews, Rating, ReviewSubject, ReviewsByReviewer,
+};
+use frame_support::{
+	sp_runtime::traits::Saturating,
+	storage::migration::remove_storage_prefix,
+	traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+};
+
+/// Runs every migration the on chain storage version has not seen yet.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight = T::DbWeight::get().reads(1);
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+	}
+
+	if Pallet::<T>::on_chain_storage_version() < 2 {
+		weight = weight.saturating_add(v2::migrate::<T>());
+	}
+
+	if Pallet::<T>::on_chain_storage_version() < 3 {
+		weight = weight.saturating_add(v3::migrate::<T>());
+	}
+
+	weight.saturating_add(seed_reward_period::<T>())
+}
+
+/// Chains that ran before the rewards existed have no end of the first reward period, which
+/// would close it on the very next block. It ends a full period after the upgrade instead.
+pub fn seed_reward_period<T: Config>() -> Weight {
+	if NextRewardAt::<T>::exists() {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let now = <frame_system::Pallet<T>>::block_number();
+	NextRewardAt::<T>::put(now.saturating_add(T::RewardPeriod::get()));
+
+	T::DbWeight::get().reads_writes(2, 1)
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// Likes used to be kept per reviewed subject. They cannot be attributed to a single review,
+	/// so they are dropped.
+	pub fn migrate<T: Config>() -> Weight {
+		let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+
+		for item in [
+			&b"PropertyReviewLikes"[..],
+			&b"ProReviewsLikes"[..],
+			&b"PropertyReviewsLikesCnt"[..],
+			&b"ProReviewsLikesCnt"[..],
+		] {
+			remove_storage_prefix(pallet, item, &[]);
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().writes(5)
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// Averages used to be stored as truncated integers. The exact totals are rebuilt from the
+	/// reviews themselves while `v3::migrate_batch` moves them, so that no single block has to
+	/// read every legacy review. `ProRate` is kept and rewritten from the totals.
+	pub fn migrate<T: Config>() -> Weight {
+		let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+
+		remove_storage_prefix(pallet, b"PropertyRate", &[]);
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().writes(2)
+	}
+}
+
+pub mod v3 {
+	use super::*;
+	use frame_support::sp_runtime::traits::Zero;
+
+	/// Reviews move from one vector per subject into a double map keyed by subject and reviewer,
+	/// and the rating totals of each subject are rebuilt from the reviews moved.
+	/// There can be too many of them for a single block, so the upgrade only flags the migration
+	/// and `migrate_batch` moves them from `on_initialize`.
+	pub fn migrate<T: Config>() -> Weight {
+		MigrationPending::<T>::put(true);
+
+		T::DbWeight::get().writes(1)
+	}
+
+	/// Moves up to `MigrationBatchSize` legacy reviews and bumps the storage version once both
+	/// legacy maps are empty. A subject with more reviews than that is moved over several
+	/// blocks, `MigrationOffset` tells how many of its reviews are already done. Legacy reviews
+	/// carry no creation block, so they get block zero.
+	pub fn migrate_batch<T: Config>() -> Weight {
+		let limit = T::MigrationBatchSize::get();
+		let mut offset = Pallet::<T>::migration_offset();
+		let mut moved: u32 = 0;
+		let mut reads: u64 = 2;
+		let mut writes: u64 = 1;
+
+		while moved < limit {
+			reads += 1;
+
+			if let Some((property_id, reviews)) = PropertyReview::<T>::iter().next() {
+				let (start, end) = page(reviews.len(), offset, limit - moved);
+				let mut rating = match start {
+					0 => Rating::default(),
+					_ => Pallet::<T>::property_rating(&property_id),
+				};
+
+				for (reviewer, rate, review) in reviews[start..end].iter().cloned() {
+					let _ = rating.add(rate);
+					PropertyReviews::<T>::insert(
+						&property_id,
+						&reviewer,
+						(rate, review, Zero::zero()),
+					);
+					ReviewsByReviewer::<T>::insert(
+						reviewer,
+						ReviewSubject::Property(property_id),
+						Zero::zero(),
+					);
+				}
+				PropertyRating::<T>::insert(&property_id, rating);
+
+				if end == reviews.len() {
+					PropertyReview::<T>::remove(&property_id);
+					offset = 0;
+				} else {
+					offset = end as u32;
+				}
+
+				moved += (end - start).max(1) as u32;
+				reads += 1;
+				writes += 2 * (end - start) as u64 + 2;
+				continue;
+			}
+
+			reads += 1;
+
+			if let Some((pro_id, reviews)) = ProReview::<T>::iter().next() {
+				let (start, end) = page(reviews.len(), offset, limit - moved);
+				let mut rating = match start {
+					0 => Rating::default(),
+					_ => Pallet::<T>::pro_rating(&pro_id),
+				};
+
+				for (reviewer, rate, review) in reviews[start..end].iter().cloned() {
+					let _ = rating.add(rate);
+					ProReviews::<T>::insert(&pro_id, &reviewer, (rate, review, Zero::zero()));
+					ReviewsByReviewer::<T>::insert(
+						reviewer,
+						ReviewSubject::Pro(pro_id.clone()),
+						Zero::zero(),
+					);
+				}
+				Pallet::<T>::put_pro_rating(&pro_id, rating);
+
+				if end == reviews.len() {
+					ProReview::<T>::remove(&pro_id);
+					offset = 0;
+				} else {
+					offset = end as u32;
+				}
+
+				moved += (end - start).max(1) as u32;
+				reads += 1;
+				writes += 2 * (end - start) as u64 + 3;
+				continue;
+			}
+
+			MigrationPending::<T>::kill();
+			MigrationOffset::<T>::kill();
+			StorageVersion::new(3).put::<Pallet<T>>();
+			writes += 2;
+
+			return T::DbWeight::get().reads_writes(reads, writes);
+		}
+
+		MigrationOffset::<T>::put(offset);
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Range of the next at most `limit` reviews of a subject with `len` reviews.
+	fn page(len: usize, offset: u32, limit: u32) -> (usize, usize) {
+		let start = (offset as usize).min(len);
+
+		(start, len.min(start + limit as usize))
+	}
+}
@@ -1,11 +1,19 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
-use frame_system as system;
+use crate as pallet_rate_review;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, GenesisBuild},
+	PalletId,
+};
+use frame_system::{self as system, EnsureRoot};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
+use std::{
+	cell::RefCell,
+	collections::{BTreeMap, BTreeSet},
+};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -18,7 +26,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		RateReview: pallet_rate_review::{Pallet, Call, Storage, Config, Event<T>},
 	}
 );
 
@@ -40,7 +49,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -49,11 +58,161 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
 	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const RewardPalletId: PalletId = PalletId(*b"rp/rwrds");
+}
+
+thread_local! {
+	static PROPERTY_OWNERS: RefCell<BTreeMap<H256, u64>> = RefCell::new(BTreeMap::new());
+	static PROFESSIONS: RefCell<BTreeMap<u64, u8>> = RefCell::new(BTreeMap::new());
+	static STANDING: RefCell<BTreeMap<u64, (u64, u32)>> = RefCell::new(BTreeMap::new());
+	static CONNECTIONS: RefCell<BTreeSet<(u64, u64)>> = RefCell::new(BTreeSet::new());
+}
+
+/// Properties, pros and connections kept in memory, set up by the tests. Like the runtime, only
+/// connected accounts may review a pro.
+pub struct MockWorld;
+
+impl MockWorld {
+	pub fn set_property_owner(property_id: H256, owner: u64) {
+		PROPERTY_OWNERS.with(|x| x.borrow_mut().insert(property_id, owner));
+	}
+
+	pub fn set_profession(pro_id: u64, profession: u8) {
+		PROFESSIONS.with(|x| x.borrow_mut().insert(pro_id, profession));
+	}
+
+	pub fn set_standing(who: u64, member_since: u64, reputation: u32) {
+		STANDING.with(|x| x.borrow_mut().insert(who, (member_since, reputation)));
+	}
+
+	pub fn connect(a: u64, b: u64) {
+		CONNECTIONS.with(|x| {
+			x.borrow_mut().insert((a, b));
+			x.borrow_mut().insert((b, a));
+		});
+	}
+
+	/// The only proof the mock accepts for a review of `pro_id` by `reviewer`.
+	pub fn engagement_proof(reviewer: u64, pro_id: u64) -> u64 {
+		pro_id * 1_000 + reviewer
+	}
+}
+
+impl pallet_rate_review::PropertyOwnership<H256, u64> for MockWorld {
+	fn exists(_property_id: &H256) -> bool {
+		true
+	}
+
+	fn is_owner(property_id: &H256, who: &u64) -> bool {
+		PROPERTY_OWNERS.with(|x| x.borrow().get(property_id) == Some(who))
+	}
+}
+
+impl pallet_rate_review::ReviewEligibility<u64> for MockWorld {
+	fn can_review_pro(reviewer: &u64, pro_id: &u64) -> bool {
+		PROFESSIONS.with(|x| x.borrow().contains_key(pro_id))
+			&& CONNECTIONS.with(|x| x.borrow().contains(&(*reviewer, *pro_id)))
+	}
+}
+
+impl pallet_rate_review::ProProfession<u64, u8> for MockWorld {
+	fn profession(pro_id: &u64) -> Option<u8> {
+		PROFESSIONS.with(|x| x.borrow().get(pro_id).copied())
+	}
+}
+
+impl pallet_rate_review::ReviewerStanding<u64, u64> for MockWorld {
+	fn member_since(reviewer: &u64) -> Option<u64> {
+		STANDING.with(|x| x.borrow().get(reviewer).map(|x| x.0))
+	}
+
+	fn reputation(reviewer: &u64) -> u32 {
+		STANDING.with(|x| x.borrow().get(reviewer).map_or(0, |x| x.1))
+	}
+
+	fn is_connected(reviewer: &u64, pro_id: &u64) -> bool {
+		CONNECTIONS.with(|x| x.borrow().contains(&(*reviewer, *pro_id)))
+	}
+}
+
+impl pallet_rate_review::VerifyEngagement<u64, H256, u64> for MockWorld {
+	fn verify(
+		reviewer: &u64,
+		subject: &pallet_rate_review::ReviewSubject<u64, H256>,
+		proof: &u64,
+	) -> bool {
+		match subject {
+			pallet_rate_review::ReviewSubject::Pro(pro_id) => {
+				*proof == Self::engagement_proof(*reviewer, *pro_id)
+			},
+			pallet_rate_review::ReviewSubject::Property(_) => false,
+		}
+	}
+}
+
+impl pallet_rate_review::Config for Test {
+	type Event = Event;
+	type PropertyOwnership = MockWorld;
+	type ReviewEligibility = MockWorld;
+	type EngagementProof = u64;
+	type EngagementVerifier = MockWorld;
+	type ModerationOrigin = EnsureRoot<u64>;
+	type ReportThreshold = ConstU32<3>;
+	type MaxReportsPerReview = ConstU32<5>;
+	type Currency = Balances;
+	type ReviewDeposit = ConstU64<10>;
+	type DepositChallengePeriod = ConstU64<10>;
+	type Slashed = ();
+	type ReviewPeriod = ConstU64<100>;
+	type MaxReviewsPerPeriod = ConstU32<10>;
+	type SurgeWindow = ConstU64<10>;
+	type SurgeThreshold = ConstU32<100>;
+	type SurgeLockPeriod = ConstU64<100>;
+	type RewardPalletId = RewardPalletId;
+	type RewardPeriod = ConstU64<1_000>;
+	type RewardLikeThreshold = ConstU32<10>;
+	type MaxPayoutsPerBlock = ConstU32<10>;
+	type MaxTipMemoLength = ConstU32<32>;
+	type Profession = u8;
+	type ProProfession = MockWorld;
+	type MaxRatingDimensions = ConstU32<4>;
+	type MaxDimensionNameLength = ConstU32<16>;
+	type ReviewerStanding = MockWorld;
+	type ReviewerMaturity = ConstU64<10>;
+	type RatingHalfLife = ConstU64<100>;
+	type MaxReviewLength = ConstU32<64>;
+	type MaxReplyLength = ConstU32<64>;
+	type MaxReviewEdits = ConstU32<3>;
+	type MigrationBatchSize = ConstU32<2>;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	PROPERTY_OWNERS.with(|x| x.borrow_mut().clear());
+	PROFESSIONS.with(|x| x.borrow_mut().clear());
+	STANDING.with(|x| x.borrow_mut().clear());
+	CONNECTIONS.with(|x| x.borrow_mut().clear());
+
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	GenesisBuild::<Test>::assimilate_storage(
+		&pallet_rate_review::GenesisConfig::default(),
+		&mut storage,
+	)
+	.unwrap();
+
+	storage.into()
 }
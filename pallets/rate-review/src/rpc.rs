@@ -0,0 +1,220 @@
+use crate::{
+	BalanceOf, Config, Pallet, ProReviews, PropertyReviews, Rating, ReviewEntry, ReviewSubject,
+	ReviewsByReviewer,
+};
+use codec::{Decode, Encode};
+use frame_support::sp_runtime::FixedU128;
+use rp_profile::Content;
+use scale_info::prelude::vec::Vec;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Encode, Decode, Clone, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct RatingSummary {
+	pub average: FixedU128,
+	/// Average weighted by reviewer standing and review age, only kept for pros.
+	pub weighted_average: Option<FixedU128>,
+	pub count: u32,
+	/// Number of reviews per rate, from 0 to 5 stars.
+	pub histogram: Vec<u32>,
+}
+
+impl From<Rating> for RatingSummary {
+	fn from(rating: Rating) -> Self {
+		Self {
+			average: rating.average(),
+			weighted_average: None,
+			count: rating.count,
+			histogram: rating.histogram.to_vec(),
+		}
+	}
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub enum ReviewSort {
+	Newest,
+	/// Most liked first.
+	Helpful,
+	/// Highest rate first.
+	Rating,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct ReviewView<AccountId, BlockNumber, Balance> {
+	pub reviewer: AccountId,
+	pub rate: u8,
+	/// Score per rating dimension, empty when the subject has no dimensions.
+	pub scores: Vec<u8>,
+	/// Short summary kept on chain.
+	pub review: Vec<u8>,
+	/// Full body and media stored off chain.
+	pub content: Content,
+	pub created: BlockNumber,
+	pub edited: Option<BlockNumber>,
+	pub likes: u32,
+	/// Sum of the tips the review earned.
+	pub tips: Balance,
+	/// Hidden by moderation and left out of the rating.
+	pub hidden: bool,
+	/// Backed by a verified engagement.
+	pub verified: bool,
+	/// Author and text of the reply to the review.
+	pub reply: Option<(AccountId, Vec<u8>)>,
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn get_pro_rating(pro_id: T::AccountId) -> RatingSummary {
+		RatingSummary {
+			weighted_average: Some(Self::weighted_pro_rating(&pro_id)),
+			..Self::pro_rating(&pro_id).into()
+		}
+	}
+
+	pub fn get_property_rating(property_id: T::Hash) -> RatingSummary {
+		Self::property_rating(&property_id).into()
+	}
+
+	/// Rating per dimension of the pro's profession, together with the dimension name.
+	pub fn get_pro_dimension_ratings(pro_id: T::AccountId) -> Vec<(Vec<u8>, RatingSummary)> {
+		Self::dimensions_of(&pro_id)
+			.into_iter()
+			.enumerate()
+			.map(|(i, (name, _))| {
+				(name.into_inner(), Self::pro_dimension_rating(&pro_id, i as u32).into())
+			})
+			.collect()
+	}
+
+	pub fn get_pro_reviews(
+		pro_id: T::AccountId,
+		sort: ReviewSort,
+		verified_only: bool,
+		offset: u64,
+		limit: u16,
+	) -> Vec<ReviewView<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+		let reviews = ProReviews::<T>::iter_prefix(&pro_id)
+			.filter(|x| !Self::is_hidden(&ReviewSubject::Pro(pro_id.clone()), &x.0))
+			.map(|(reviewer, review)| Self::pro_review_view(&pro_id, reviewer, review))
+			.filter(|x| !verified_only || x.verified)
+			.collect();
+
+		Self::paginate(reviews, sort, offset, limit)
+	}
+
+	pub fn get_property_reviews(
+		property_id: T::Hash,
+		sort: ReviewSort,
+		verified_only: bool,
+		offset: u64,
+		limit: u16,
+	) -> Vec<ReviewView<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+		let reviews = PropertyReviews::<T>::iter_prefix(&property_id)
+			.filter(|x| !Self::is_hidden(&ReviewSubject::Property(property_id), &x.0))
+			.map(|(reviewer, review)| Self::property_review_view(&property_id, reviewer, review))
+			.filter(|x| !verified_only || x.verified)
+			.collect();
+
+		Self::paginate(reviews, sort, offset, limit)
+	}
+
+	/// Reviews written by `reviewer`, newest first.
+	pub fn get_reviewer_reviews(
+		reviewer: T::AccountId,
+		offset: u64,
+		limit: u16,
+	) -> Vec<(
+		ReviewSubject<T::AccountId, T::Hash>,
+		ReviewView<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+	)> {
+		let mut subjects = ReviewsByReviewer::<T>::iter_prefix(&reviewer).collect::<Vec<_>>();
+		subjects.sort_by(|a, b| b.1.cmp(&a.1));
+
+		subjects
+			.into_iter()
+			.skip(offset as usize)
+			.take(limit as usize)
+			.filter_map(|(subject, _)| {
+				let view = match &subject {
+					ReviewSubject::Pro(pro_id) => Self::pro_reviews(pro_id, &reviewer)
+						.map(|x| Self::pro_review_view(pro_id, reviewer.clone(), x)),
+					ReviewSubject::Property(property_id) => {
+						Self::property_reviews(property_id, &reviewer)
+							.map(|x| Self::property_review_view(property_id, reviewer.clone(), x))
+					},
+				}?;
+				Some((subject, view))
+			})
+			.collect()
+	}
+
+	pub fn get_tips_received(account: T::AccountId) -> BalanceOf<T> {
+		Self::tips_received(&account)
+	}
+
+	fn pro_review_view(
+		pro_id: &T::AccountId,
+		reviewer: T::AccountId,
+		review: ReviewEntry<T::BlockNumber, T::MaxReviewLength>,
+	) -> ReviewView<T::AccountId, T::BlockNumber, BalanceOf<T>> {
+		let key = (pro_id, &reviewer);
+
+		ReviewView {
+			rate: review.0,
+			scores: Self::pro_review_scores(pro_id, &reviewer)
+				.map(|x| x.into_inner())
+				.unwrap_or_default(),
+			review: review.1.into_inner(),
+			content: Self::pro_review_content(key).unwrap_or_default(),
+			created: review.2,
+			edited: Self::pro_review_edited_at(pro_id, &reviewer),
+			likes: Self::pro_review_like_cnt(key),
+			tips: Self::review_tips((ReviewSubject::Pro(pro_id.clone()), &reviewer)),
+			hidden: Self::is_hidden(&ReviewSubject::Pro(pro_id.clone()), &reviewer),
+			verified: Self::is_verified(&ReviewSubject::Pro(pro_id.clone()), &reviewer),
+			reply: Self::pro_replies(key).map(|x| (x.0, x.1.into_inner())),
+			reviewer,
+		}
+	}
+
+	fn property_review_view(
+		property_id: &T::Hash,
+		reviewer: T::AccountId,
+		review: ReviewEntry<T::BlockNumber, T::MaxReviewLength>,
+	) -> ReviewView<T::AccountId, T::BlockNumber, BalanceOf<T>> {
+		let key = (property_id, &reviewer);
+
+		ReviewView {
+			rate: review.0,
+			scores: Vec::new(),
+			review: review.1.into_inner(),
+			content: Self::property_review_content(key).unwrap_or_default(),
+			created: review.2,
+			edited: Self::property_review_edited_at(property_id, &reviewer),
+			likes: Self::property_review_like_cnt(key),
+			tips: Self::review_tips((ReviewSubject::Property(*property_id), &reviewer)),
+			hidden: Self::is_hidden(&ReviewSubject::Property(*property_id), &reviewer),
+			verified: Self::is_verified(&ReviewSubject::Property(*property_id), &reviewer),
+			reply: Self::property_replies(key).map(|x| (x.0, x.1.into_inner())),
+			reviewer,
+		}
+	}
+
+	fn paginate(
+		mut reviews: Vec<ReviewView<T::AccountId, T::BlockNumber, BalanceOf<T>>>,
+		sort: ReviewSort,
+		offset: u64,
+		limit: u16,
+	) -> Vec<ReviewView<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+		match sort {
+			ReviewSort::Newest => reviews.sort_by(|a, b| b.created.cmp(&a.created)),
+			ReviewSort::Helpful => reviews.sort_by(|a, b| b.likes.cmp(&a.likes)),
+			ReviewSort::Rating => reviews.sort_by(|a, b| b.rate.cmp(&a.rate)),
+		}
+
+		reviews.into_iter().skip(offset as usize).take(limit as usize).collect()
+	}
+}
@@ -1,20 +1,1301 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations, mock::*, rpc::ReviewSort, Error, ModerationAction, NextRewardAt, Pallet, ProReview,
+	ProReviews, PropertyRating, PropertyReview, PropertyReviews, PropertyReviewsCnt, Rate, Rating,
+	ReportReason, Review, ReviewDeposits, ReviewSubject, ReviewsByReviewer, TipTarget, MAX_RATE,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{ConstU32, Currency, GetStorageVersion, Hooks, ReservableCurrency},
+	BoundedVec, WeakBoundedVec,
+};
+use rp_profile::Content;
+use sp_core::H256;
+use sp_runtime::{traits::BadOrigin, DispatchResult, FixedPointNumber, FixedU128};
+
+fn legacy_review(reviewer: u64, rate: Rate) -> Review<u64, ConstU32<64>> {
+	(reviewer, rate, WeakBoundedVec::force_from(b"review".to_vec(), None))
+}
+
+const PRO: u64 = 100;
+
+fn text(text: &[u8]) -> WeakBoundedVec<u8, ConstU32<64>> {
+	WeakBoundedVec::force_from(text.to_vec(), None)
+}
+
+fn memo(memo: &[u8]) -> BoundedVec<u8, ConstU32<32>> {
+	memo.to_vec().try_into().unwrap()
+}
+
+fn review_property(reviewer: u64, property_id: H256, rate: Rate) {
+	Balances::make_free_balance_be(&reviewer, 100);
+	assert_ok!(RateReview::create_property_review(
+		Origin::signed(reviewer),
+		property_id,
+		rate,
+		text(b"review"),
+		Content::None,
+	));
+}
+
+/// Registers `PRO` and connects `reviewer` to them, so that they may review them.
+fn review_pro(reviewer: u64, scores: Vec<Rate>) {
+	MockWorld::set_profession(PRO, 0);
+	MockWorld::connect(reviewer, PRO);
+	Balances::make_free_balance_be(&reviewer, 100);
+	assert_ok!(RateReview::create_pro_review(
+		Origin::signed(reviewer),
+		PRO,
+		scores.try_into().unwrap(),
+		text(b"review"),
+		Content::None,
+	));
+}
+
+#[test]
+fn rating_add_and_remove_keep_exact_totals() {
+	let mut rating = Rating::default();
+
+	assert_eq!(rating.add(5), Some(()));
+	assert_eq!(rating.add(4), Some(()));
+	assert_eq!(rating.add(4), Some(()));
+	assert_eq!((rating.sum, rating.count), (13, 3));
+	assert_eq!(rating.histogram, [0, 0, 0, 0, 2, 1]);
+	assert_eq!(rating.average(), FixedU128::saturating_from_rational(13, 3));
+
+	assert_eq!(rating.remove(4), Some(()));
+	assert_eq!((rating.sum, rating.count), (9, 2));
+	assert_eq!(rating.histogram, [0, 0, 0, 0, 1, 1]);
+	assert_eq!(rating.rate(), 4);
+}
+
+#[test]
+fn rating_rejects_unknown_rates_without_changes() {
+	let mut rating = Rating::default();
+	rating.add(3).unwrap();
+	let before = rating.clone();
+
+	assert_eq!(rating.add(MAX_RATE + 1), None);
+	assert_eq!(rating.remove(2), None);
+	assert_eq!(rating, before);
+
+	assert_eq!(rating.remove(3), Some(()));
+	assert_eq!(rating.remove(3), None);
+	assert_eq!(rating, Rating::default());
+	assert_eq!(rating.average(), FixedU128::from_inner(0));
+}
+
+#[test]
+fn v3_migration_moves_large_subjects_over_several_blocks() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		let pro_id = 100;
+
+		PropertyReview::<Test>::insert(
+			property_id,
+			(1..=5).map(|x| legacy_review(x, x as Rate)).collect::<Vec<_>>(),
+		);
+		ProReview::<Test>::insert(pro_id, vec![legacy_review(1, 4)]);
+		migrations::v3::migrate::<Test>();
+
+		// The batch size is 2, so the property needs three blocks.
+		migrations::v3::migrate_batch::<Test>();
+		assert_eq!(PropertyReviews::<Test>::iter_prefix(property_id).count(), 2);
+		assert_eq!(RateReview::migration_offset(), 2);
+		assert!(PropertyReview::<Test>::contains_key(property_id));
+
+		migrations::v3::migrate_batch::<Test>();
+		assert_eq!(PropertyReviews::<Test>::iter_prefix(property_id).count(), 4);
+		assert_eq!(RateReview::migration_offset(), 4);
+
+		// The last property review leaves room for the pro review.
+		migrations::v3::migrate_batch::<Test>();
+		assert_eq!(PropertyReviews::<Test>::iter_prefix(property_id).count(), 5);
+		assert!(!PropertyReview::<Test>::contains_key(property_id));
+		assert_eq!(RateReview::property_rating(property_id).sum, 15);
+		assert_eq!(RateReview::property_rating(property_id).count, 5);
+		assert!(ProReviews::<Test>::contains_key(pro_id, 1));
+		assert_eq!(RateReview::pro_rating(pro_id).sum, 4);
+		assert_eq!(RateReview::pro_rate(pro_id), 4);
+		assert!(RateReview::migration_pending());
+
+		migrations::v3::migrate_batch::<Test>();
+		assert!(!RateReview::migration_pending());
+		assert_eq!(RateReview::migration_offset(), 0);
+		assert_eq!(Pallet::<Test>::on_chain_storage_version(), 3);
+	});
+}
+
+#[test]
+fn reviewer_reviews_come_from_the_reviewer_index_by_page() {
+	new_test_ext().execute_with(|| {
+		let reviewer = 7;
+
+		for i in 1..=3u8 {
+			PropertyReview::<Test>::insert(H256::repeat_byte(i), vec![legacy_review(reviewer, i)]);
+		}
+		ProReview::<Test>::insert(100, vec![legacy_review(1, 4)]);
+		migrations::v3::migrate::<Test>();
+		while RateReview::migration_pending() {
+			migrations::v3::migrate_batch::<Test>();
+		}
+
+		assert_eq!(ReviewsByReviewer::<Test>::iter_prefix(reviewer).count(), 3);
+		assert_eq!(RateReview::get_reviewer_reviews(reviewer, 0, 10).len(), 3);
+		assert_eq!(RateReview::get_reviewer_reviews(reviewer, 1, 1).len(), 1);
+		assert!(RateReview::get_reviewer_reviews(reviewer, 3, 10).is_empty());
+		assert!(RateReview::get_reviewer_reviews(1, 0, 10)
+			.iter()
+			.all(|x| x.0 == ReviewSubject::Pro(100) && x.1.rate == 4));
+	});
+}
+
+fn reviewed_property_with_deposit(reviewer: u64) -> ReviewSubject<u64, H256> {
+	let property_id = H256::repeat_byte(1);
+	let review = WeakBoundedVec::force_from(b"review".to_vec(), None);
+
+	System::set_block_number(1);
+	Balances::make_free_balance_be(&reviewer, 100);
+	assert_ok!(Balances::reserve(&reviewer, 10));
+	PropertyReviews::<Test>::insert(property_id, reviewer, (4, review, 1));
+	PropertyRating::<Test>::mutate(property_id, |x| x.add(4).unwrap());
+	PropertyReviewsCnt::<Test>::insert(property_id, 1);
+	ReviewDeposits::<Test>::insert((ReviewSubject::Property(property_id), reviewer), (10, 11));
+
+	ReviewSubject::Property(property_id)
+}
+
+#[test]
+fn hidden_review_deposit_is_slashed_after_the_challenge_period() {
+	new_test_ext().execute_with(|| {
+		let subject = reviewed_property_with_deposit(1);
+
+		System::set_block_number(5);
+		assert_ok!(RateReview::hide_review(Origin::root(), subject.clone(), 1));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(RateReview::review_deposits((&subject, 1)).map(|x| x.1), Some(15));
+
+		System::set_block_number(11);
+		assert_noop!(
+			RateReview::release_deposit(Origin::signed(2), subject.clone(), 1),
+			Error::<Test>::ChallengePeriodNotOver
+		);
+
+		System::set_block_number(15);
+		assert_ok!(RateReview::release_deposit(Origin::signed(2), subject, 1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 90);
+	});
+}
+
+#[test]
+fn restored_review_deposit_is_returned() {
+	new_test_ext().execute_with(|| {
+		let subject = reviewed_property_with_deposit(1);
+
+		assert_ok!(RateReview::hide_review(Origin::root(), subject.clone(), 1));
+		assert_ok!(RateReview::restore_review(Origin::root(), subject.clone(), 1));
+
+		System::set_block_number(11);
+		assert_ok!(RateReview::release_deposit(Origin::signed(2), subject, 1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn removed_review_keeps_its_deposit_until_the_challenge_period_is_over() {
+	new_test_ext().execute_with(|| {
+		let subject = reviewed_property_with_deposit(1);
+
+		assert_ok!(RateReview::remove_property_review(Origin::signed(1), H256::repeat_byte(1), 0));
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		System::set_block_number(11);
+		assert_ok!(RateReview::release_deposit(Origin::signed(2), subject, 1));
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn property_owner_replies_once_per_review() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		MockWorld::set_property_owner(property_id, 9);
+		review_property(1, property_id, 4);
+
+		assert_noop!(
+			RateReview::reply_to_property_review(Origin::signed(2), property_id, 1, text(b"no")),
+			Error::<Test>::NotPropertyOwner
+		);
+		assert_noop!(
+			RateReview::reply_to_property_review(Origin::signed(9), property_id, 2, text(b"no")),
+			Error::<Test>::PropertyReviewNotFound
+		);
+
+		assert_ok!(RateReview::reply_to_property_review(
+			Origin::signed(9),
+			property_id,
+			1,
+			text(b"thanks")
+		));
+		assert_eq!(RateReview::property_replies((property_id, 1)), Some((9, text(b"thanks"))));
+		assert_eq!(RateReview::property_reply_cnt(property_id), 1);
+		assert_noop!(
+			RateReview::reply_to_property_review(Origin::signed(9), property_id, 1, text(b"again")),
+			Error::<Test>::PropertyReplyAlreadyCreated
+		);
+
+		assert_ok!(RateReview::edit_property_reply(
+			Origin::signed(9),
+			property_id,
+			1,
+			text(b"thank you")
+		));
+		assert_eq!(RateReview::property_replies((property_id, 1)), Some((9, text(b"thank you"))));
+
+		assert_ok!(RateReview::remove_property_reply(Origin::signed(9), property_id, 1));
+		assert_eq!(RateReview::property_replies((property_id, 1)), None);
+		assert_eq!(RateReview::property_reply_cnt(property_id), 0);
+		assert_noop!(
+			RateReview::edit_property_reply(Origin::signed(9), property_id, 1, text(b"x")),
+			Error::<Test>::PropertyReplyNotFound
+		);
+	});
+}
+
+#[test]
+fn pro_replies_to_reviews_of_themselves() {
+	new_test_ext().execute_with(|| {
+		review_pro(1, vec![4]);
+
+		assert_noop!(
+			RateReview::reply_to_pro_review(Origin::signed(2), 1, text(b"no")),
+			Error::<Test>::ProReviewNotFound
+		);
+
+		assert_ok!(RateReview::reply_to_pro_review(Origin::signed(PRO), 1, text(b"thanks")));
+		assert_eq!(RateReview::pro_replies((PRO, 1)), Some((PRO, text(b"thanks"))));
+		assert_eq!(RateReview::pro_reply_cnt(PRO), 1);
+		assert_noop!(
+			RateReview::reply_to_pro_review(Origin::signed(PRO), 1, text(b"again")),
+			Error::<Test>::ProReplyAlreadyCreated
+		);
+
+		assert_ok!(RateReview::edit_pro_reply(Origin::signed(PRO), 1, text(b"thank you")));
+		assert_eq!(RateReview::pro_replies((PRO, 1)), Some((PRO, text(b"thank you"))));
+
+		assert_ok!(RateReview::remove_pro_reply(Origin::signed(PRO), 1));
+		assert_eq!(RateReview::pro_reply_cnt(PRO), 0);
+		assert_noop!(
+			RateReview::remove_pro_reply(Origin::signed(PRO), 1),
+			Error::<Test>::ProReplyNotFound
+		);
+	});
+}
+
+#[test]
+fn removing_a_review_removes_its_reply() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		MockWorld::set_property_owner(property_id, 9);
+		review_property(1, property_id, 4);
+		review_property(2, property_id, 5);
+		assert_ok!(RateReview::reply_to_property_review(
+			Origin::signed(9),
+			property_id,
+			1,
+			text(b"thanks")
+		));
+		assert_ok!(RateReview::reply_to_property_review(
+			Origin::signed(9),
+			property_id,
+			2,
+			text(b"thanks")
+		));
+
+		assert_ok!(RateReview::remove_property_review(Origin::signed(1), property_id, 0));
+
+		assert_eq!(RateReview::property_replies((property_id, 1)), None);
+		assert!(RateReview::property_replies((property_id, 2)).is_some());
+		assert_eq!(RateReview::property_reply_cnt(property_id), 1);
+	});
+}
+
+#[test]
+fn likes_are_counted_per_review() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		review_property(1, property_id, 4);
+		review_property(2, property_id, 5);
+
+		assert_ok!(RateReview::like_property_review(Origin::signed(3), property_id, 1));
+		assert_ok!(RateReview::like_property_review(Origin::signed(4), property_id, 1));
+		assert_ok!(RateReview::like_property_review(Origin::signed(3), property_id, 2));
+
+		assert_eq!(RateReview::property_review_like_cnt((property_id, 1)), 2);
+		assert_eq!(RateReview::property_review_like_cnt((property_id, 2)), 1);
+		assert!(RateReview::property_review_likers((property_id, 1), 4));
+		assert!(!RateReview::property_review_likers((property_id, 2), 4));
+
+		assert_noop!(
+			RateReview::like_property_review(Origin::signed(3), property_id, 1),
+			Error::<Test>::PropertyReviewAlreadyLiked
+		);
+		assert_noop!(
+			RateReview::like_property_review(Origin::signed(1), property_id, 1),
+			Error::<Test>::CannotLikeOwnReview
+		);
+		assert_noop!(
+			RateReview::like_property_review(Origin::signed(3), property_id, 5),
+			Error::<Test>::PropertyReviewNotFound
+		);
+
+		assert_ok!(RateReview::unlike_property_review(Origin::signed(3), property_id, 1));
+		assert_eq!(RateReview::property_review_like_cnt((property_id, 1)), 1);
+		assert_noop!(
+			RateReview::unlike_property_review(Origin::signed(3), property_id, 1),
+			Error::<Test>::PropertyReviewNotLiked
+		);
+	});
+}
+
+#[test]
+fn pro_review_likes_are_counted_per_review() {
+	new_test_ext().execute_with(|| {
+		review_pro(1, vec![4]);
+		review_pro(2, vec![3]);
+
+		assert_ok!(RateReview::like_pro_review(Origin::signed(3), PRO, 1));
+		assert_ok!(RateReview::like_pro_review(Origin::signed(PRO), PRO, 1));
+
+		assert_eq!(RateReview::pro_review_like_cnt((PRO, 1)), 2);
+		assert_eq!(RateReview::pro_review_like_cnt((PRO, 2)), 0);
+		assert_noop!(
+			RateReview::like_pro_review(Origin::signed(3), PRO, 1),
+			Error::<Test>::ProReviewAlreadyLiked
+		);
+		assert_noop!(
+			RateReview::like_pro_review(Origin::signed(2), PRO, 2),
+			Error::<Test>::CannotLikeOwnReview
+		);
+
+		assert_ok!(RateReview::unlike_pro_review(Origin::signed(3), PRO, 1));
+		assert_eq!(RateReview::pro_review_like_cnt((PRO, 1)), 1);
+		assert_noop!(
+			RateReview::unlike_pro_review(Origin::signed(3), PRO, 1),
+			Error::<Test>::ProReviewNotLiked
+		);
+	});
+}
+
+#[test]
+fn hidden_reviews_cannot_be_liked() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		review_property(1, property_id, 4);
+		assert_ok!(RateReview::hide_review(
+			Origin::root(),
+			ReviewSubject::Property(property_id),
+			1
+		));
+
+		assert_noop!(
+			RateReview::like_property_review(Origin::signed(3), property_id, 1),
+			Error::<Test>::ReviewIsHidden
+		);
+	});
+}
+
+#[test]
+fn edits_replace_the_rate_exactly_and_keep_the_history() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		review_property(1, property_id, 2);
+		review_property(2, property_id, 5);
+
+		System::set_block_number(3);
+		assert_ok!(RateReview::edit_property_review(
+			Origin::signed(1),
+			property_id,
+			4,
+			text(b"better"),
+			Content::None,
+		));
+
+		assert_eq!(RateReview::property_rating(property_id).sum, 9);
+		assert_eq!(RateReview::property_rating(property_id).histogram, [0, 0, 0, 0, 1, 1]);
+		assert_eq!(RateReview::property_reviews_cnt(property_id), 2);
+		assert_eq!(RateReview::property_reviews(property_id, 1), Some((4, text(b"better"), 0)));
+		assert_eq!(
+			RateReview::property_review_edits((property_id, 1)).into_inner(),
+			vec![(3, 2, text(b"review"))]
+		);
+
+		for _ in 0..2 {
+			assert_ok!(RateReview::edit_property_review(
+				Origin::signed(1),
+				property_id,
+				4,
+				text(b"again"),
+				Content::None,
+			));
+		}
+		assert_noop!(
+			RateReview::edit_property_review(
+				Origin::signed(1),
+				property_id,
+				1,
+				text(b"too many"),
+				Content::None,
+			),
+			Error::<Test>::ReviewEditLimitReached
+		);
+	});
+}
+
+#[test]
+fn pro_review_edits_replace_the_rate_exactly() {
+	new_test_ext().execute_with(|| {
+		review_pro(1, vec![2]);
+		review_pro(2, vec![5]);
+
+		assert_ok!(RateReview::edit_pro_review(
+			Origin::signed(1),
+			PRO,
+			vec![3].try_into().unwrap(),
+			text(b"better"),
+			Content::None,
+		));
+
+		assert_eq!(RateReview::pro_rating(PRO).sum, 8);
+		assert_eq!(RateReview::pro_rate(PRO), 4);
+		assert_eq!(RateReview::pro_reviews_cnt(PRO), 2);
+		assert_eq!(RateReview::pro_review_edits((PRO, 1)).len(), 1);
+	});
+}
+
+#[test]
+fn edits_of_hidden_reviews_stay_out_of_the_rating() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		review_property(1, property_id, 2);
+		review_property(2, property_id, 5);
+		assert_ok!(RateReview::hide_review(
+			Origin::root(),
+			ReviewSubject::Property(property_id),
+			1
+		));
+
+		assert_ok!(RateReview::edit_property_review(
+			Origin::signed(1),
+			property_id,
+			4,
+			text(b"better"),
+			Content::None,
+		));
+		assert_eq!(RateReview::property_rating(property_id).sum, 5);
+		assert_eq!(RateReview::property_reviews_cnt(property_id), 1);
+
+		assert_ok!(RateReview::restore_review(
+			Origin::root(),
+			ReviewSubject::Property(property_id),
+			1
+		));
+		assert_eq!(RateReview::property_rating(property_id).sum, 9);
+		assert_eq!(RateReview::property_reviews_cnt(property_id), 2);
+	});
+}
+
+#[test]
+fn removal_recomputes_the_rating_and_drops_the_likes() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		review_property(1, property_id, 2);
+		review_property(2, property_id, 5);
+		assert_ok!(RateReview::like_property_review(Origin::signed(3), property_id, 1));
+		assert_ok!(RateReview::like_property_review(Origin::signed(4), property_id, 1));
+
+		assert_ok!(RateReview::remove_property_review(Origin::signed(1), property_id, 2));
+
+		assert_eq!(RateReview::property_reviews(property_id, 1), None);
+		assert_eq!(RateReview::property_rating(property_id).sum, 5);
+		assert_eq!(RateReview::property_rating(property_id).count, 1);
+		assert_eq!(RateReview::property_reviews_cnt(property_id), 1);
+		assert_eq!(RateReview::property_review_like_cnt((property_id, 1)), 0);
+		assert!(!RateReview::property_review_likers((property_id, 1), 3));
+		assert_eq!(RateReview::reviews_by_reviewer(1, ReviewSubject::Property(property_id)), None);
+
+		// The review can be written again.
+		review_property(1, property_id, 3);
+		assert_eq!(RateReview::property_rating(property_id).sum, 8);
+	});
+}
+
+#[test]
+fn too_low_like_witness_changes_nothing() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		review_property(1, property_id, 2);
+		assert_ok!(RateReview::like_property_review(Origin::signed(3), property_id, 1));
+		review_pro(1, vec![4]);
+		assert_ok!(RateReview::like_pro_review(Origin::signed(3), PRO, 1));
+
+		assert_noop!(
+			RateReview::remove_property_review(Origin::signed(1), property_id, 0),
+			Error::<Test>::LikeWitnessTooLow
+		);
+		assert_noop!(
+			RateReview::remove_pro_review(Origin::signed(1), PRO, 0),
+			Error::<Test>::LikeWitnessTooLow
+		);
+
+		assert_ok!(RateReview::remove_pro_review(Origin::signed(1), PRO, 1));
+		assert_eq!(RateReview::pro_rating(PRO).count, 0);
+		assert_eq!(RateReview::pro_rate(PRO), 0);
+	});
+}
+
+fn set_dimensions(profession: u8, dimensions: &[(&[u8], u8)]) -> DispatchResult {
+	RateReview::set_rating_dimensions(
+		Origin::root(),
+		profession,
+		dimensions
+			.iter()
+			.map(|(name, weight)| (name.to_vec().try_into().unwrap(), *weight))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+	)
+}
+
+fn create_pro_review(reviewer: u64, scores: Vec<Rate>) -> DispatchResult {
+	MockWorld::connect(reviewer, PRO);
+	Balances::make_free_balance_be(&reviewer, 100);
+	RateReview::create_pro_review(
+		Origin::signed(reviewer),
+		PRO,
+		scores.try_into().unwrap(),
+		text(b"review"),
+		Content::None,
+	)
+}
+
+#[test]
+fn set_rating_dimensions_needs_root_and_a_weighted_dimension() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RateReview::set_rating_dimensions(Origin::signed(1), 1, Default::default()),
+			BadOrigin
+		);
+		assert_noop!(
+			set_dimensions(1, &[(b"quality", 0), (b"price", 0)]),
+			Error::<Test>::InvalidDimensions
+		);
+
+		assert_ok!(set_dimensions(1, &[(b"quality", 3), (b"price", 0)]));
+		assert_eq!(RateReview::rating_dimensions(1).len(), 2);
+
+		assert_ok!(set_dimensions(1, &[]));
+		assert!(RateReview::rating_dimensions(1).is_empty());
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn pro_reviews_score_every_dimension_of_the_profession() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		MockWorld::set_profession(PRO, 1);
+		assert_ok!(set_dimensions(1, &[(b"quality", 3), (b"price", 1)]));
+
+		assert_noop!(create_pro_review(1, vec![4]), Error::<Test>::InvalidScores);
+		assert_noop!(create_pro_review(1, vec![4, 4, 4]), Error::<Test>::InvalidScores);
+		assert_noop!(create_pro_review(1, vec![4, MAX_RATE + 1]), Error::<Test>::InvalidRate);
+
+		// (5 * 3 + 1 * 1) / 4 = 4
+		assert_ok!(create_pro_review(1, vec![5, 1]));
+		assert_eq!(RateReview::pro_reviews(PRO, 1).unwrap().0, 4);
+		assert_eq!(RateReview::pro_review_scores(PRO, 1).unwrap().into_inner(), vec![5, 1]);
+		assert_eq!(RateReview::pro_dimension_rating(PRO, 0).sum, 5);
+		assert_eq!(RateReview::pro_dimension_rating(PRO, 1).sum, 1);
+
+		// (2 * 3 + 5 * 1) / 4 = 2.75, rounded to 3
+		assert_ok!(RateReview::edit_pro_review(
+			Origin::signed(1),
+			PRO,
+			vec![2, 5].try_into().unwrap(),
+			text(b"edited"),
+			Content::None,
+		));
+		assert_eq!(RateReview::pro_reviews(PRO, 1).unwrap().0, 3);
+		assert_eq!(RateReview::pro_rating(PRO).sum, 3);
+		assert_eq!(RateReview::pro_dimension_rating(PRO, 0).sum, 2);
+		assert_eq!(RateReview::pro_dimension_rating(PRO, 1).sum, 5);
+		assert_eq!(RateReview::pro_dimension_rating(PRO, 1).count, 1);
+
+		assert_ok!(RateReview::remove_pro_review(Origin::signed(1), PRO, 0));
+		assert_eq!(RateReview::pro_review_scores(PRO, 1), None);
+		assert_eq!(RateReview::pro_dimension_rating(PRO, 0), Rating::default());
+		assert_eq!(RateReview::pro_dimension_rating(PRO, 1), Rating::default());
+	});
+}
+
+#[test]
+fn pros_without_dimensions_take_a_single_rate() {
+	new_test_ext().execute_with(|| {
+		MockWorld::set_profession(PRO, 2);
+
+		assert_noop!(create_pro_review(1, vec![4, 4]), Error::<Test>::InvalidScores);
+		assert_ok!(create_pro_review(1, vec![4]));
+
+		assert_eq!(RateReview::pro_rate(PRO), 4);
+		assert_eq!(RateReview::pro_review_scores(PRO, 1), None);
+	});
+}
+
+#[test]
+fn only_connected_accounts_may_review_a_pro() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		let create = |reviewer: u64, pro_id: u64| {
+			RateReview::create_pro_review(
+				Origin::signed(reviewer),
+				pro_id,
+				vec![4].try_into().unwrap(),
+				text(b"review"),
+				Content::None,
+			)
+		};
+
+		// Not a pro yet.
+		MockWorld::connect(1, PRO);
+		assert_noop!(create(1, PRO), Error::<Test>::NotEligibleToReview);
+
+		MockWorld::set_profession(PRO, 0);
+		assert_noop!(create(2, PRO), Error::<Test>::NotEligibleToReview);
+		assert_noop!(create(PRO, PRO), Error::<Test>::CannotReviewSelf);
+
+		assert_ok!(create(1, PRO));
+		assert_noop!(create(1, PRO), Error::<Test>::ProReviewAlreadyCreated);
+	});
+}
+
+#[test]
+fn owners_cannot_review_their_own_property() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		MockWorld::set_property_owner(property_id, 1);
+		Balances::make_free_balance_be(&1, 100);
+
+		assert_noop!(
+			RateReview::create_property_review(
+				Origin::signed(1),
+				property_id,
+				4,
+				text(b"review"),
+				Content::None,
+			),
+			Error::<Test>::CannotReviewSelf
+		);
+
+		review_property(2, property_id, 4);
+		assert_noop!(
+			RateReview::create_property_review(
+				Origin::signed(2),
+				property_id,
+				4,
+				text(b"review"),
+				Content::None,
+			),
+			Error::<Test>::PropertyReviewAlreadyCreated
+		);
+	});
+}
+
+#[test]
+fn reports_escalate_a_review_once_they_reach_the_threshold() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		let subject = ReviewSubject::Property(property_id);
+		review_property(1, property_id, 1);
+
+		let report = |reporter: u64| {
+			RateReview::report_review(
+				Origin::signed(reporter),
+				subject.clone(),
+				1,
+				ReportReason::Fake,
+			)
+		};
+
+		assert_noop!(report(1), Error::<Test>::CannotReportOwnReview);
+		assert_noop!(
+			RateReview::report_review(Origin::signed(2), subject.clone(), 3, ReportReason::Spam),
+			Error::<Test>::ReviewNotFound
+		);
+
+		assert_ok!(report(2));
+		assert_noop!(report(2), Error::<Test>::ReviewAlreadyReported);
+		assert_ok!(report(3));
+		assert_eq!(RateReview::pending_moderation((&subject, 1)), None);
+
+		System::set_block_number(2);
+		assert_ok!(report(4));
+		assert_eq!(RateReview::report_cnt((&subject, 1)), 3);
+		assert_eq!(RateReview::pending_moderation((&subject, 1)), Some(2));
+		assert_eq!(RateReview::reports((&subject, 1), 4), Some(ReportReason::Fake));
+
+		// Escalated reviews cannot be withdrawn before a moderator looked at them.
+		assert_noop!(
+			RateReview::remove_property_review(Origin::signed(1), property_id, 0),
+			Error::<Test>::ReviewUnderModeration
+		);
+
+		assert_ok!(report(5));
+		assert_ok!(report(6));
+		assert_noop!(report(7), Error::<Test>::ReportLimitReached);
+	});
+}
+
+#[test]
+fn moderators_hide_restore_and_dismiss_with_a_log() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		let subject = ReviewSubject::Property(property_id);
+		review_property(1, property_id, 1);
+		review_property(2, property_id, 5);
+		for reporter in 3..6 {
+			assert_ok!(RateReview::report_review(
+				Origin::signed(reporter),
+				subject.clone(),
+				1,
+				ReportReason::Abusive,
+			));
+		}
+
+		assert_noop!(RateReview::hide_review(Origin::signed(3), subject.clone(), 1), BadOrigin);
+		assert_noop!(
+			RateReview::restore_review(Origin::root(), subject.clone(), 1),
+			Error::<Test>::ReviewNotHidden
+		);
+
+		assert_ok!(RateReview::hide_review(Origin::root(), subject.clone(), 1));
+		assert_noop!(
+			RateReview::hide_review(Origin::root(), subject.clone(), 1),
+			Error::<Test>::ReviewAlreadyHidden
+		);
+		assert_eq!(RateReview::pending_moderation((&subject, 1)), None);
+		assert_eq!(RateReview::property_rating(property_id).sum, 5);
+		assert_eq!(RateReview::property_reviews_cnt(property_id), 1);
+
+		System::set_block_number(3);
+		assert_ok!(RateReview::restore_review(Origin::root(), subject.clone(), 1));
+		assert_eq!(RateReview::property_rating(property_id).sum, 6);
+		assert_eq!(RateReview::report_cnt((&subject, 1)), 0);
+
+		assert_noop!(
+			RateReview::dismiss_reports(Origin::root(), subject.clone(), 1),
+			Error::<Test>::NoReports
+		);
+		assert_ok!(RateReview::report_review(
+			Origin::signed(3),
+			subject.clone(),
+			1,
+			ReportReason::Other,
+		));
+		assert_ok!(RateReview::dismiss_reports(Origin::root(), subject.clone(), 1));
+		assert_eq!(RateReview::report_cnt((&subject, 1)), 0);
+		assert_eq!(RateReview::reports((&subject, 1), 3), None);
+
+		assert_eq!(RateReview::moderation_log_cnt(), 3);
+		let actions: Vec<_> = (0..3)
+			.map(|id| {
+				let record = RateReview::moderation_log(id).unwrap();
+				assert_eq!((record.subject, record.reviewer), (subject.clone(), 1));
+				(record.action, record.block)
+			})
+			.collect();
+		assert_eq!(
+			actions,
+			vec![
+				(ModerationAction::Hidden, 1),
+				(ModerationAction::Restored, 3),
+				(ModerationAction::ReportsDismissed, 3),
+			]
+		);
+	});
+}
+
+#[test]
+fn creating_a_review_reserves_its_deposit() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		let subject = ReviewSubject::Property(property_id);
+
+		Balances::make_free_balance_be(&1, 5);
+		assert!(RateReview::create_property_review(
+			Origin::signed(1),
+			property_id,
+			4,
+			text(b"review"),
+			Content::None,
+		)
+		.is_err());
+		assert_eq!(RateReview::property_reviews(property_id, 1), None);
+
+		System::set_block_number(2);
+		review_property(1, property_id, 4);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(RateReview::review_deposits((&subject, 1)), Some((10, 12)));
+
+		assert_noop!(
+			RateReview::release_deposit(Origin::signed(2), subject.clone(), 2),
+			Error::<Test>::DepositNotFound
+		);
+
+		System::set_block_number(12);
+		assert_ok!(RateReview::release_deposit(Origin::signed(2), subject.clone(), 1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(RateReview::review_deposits((&subject, 1)), None);
+		// The review itself stays.
+		assert!(RateReview::property_reviews(property_id, 1).is_some());
+	});
+}
+
+#[test]
+fn weighted_pro_rating_favours_established_verified_and_recent_reviews() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		// A mature reviewer with full reputation weighs 4, a newcomer 1.
+		MockWorld::set_standing(1, 0, 100);
+		review_pro(1, vec![5]);
+		review_pro(2, vec![2]);
+
+		// (4 * 5 + 1 * 2) / 5
+		assert_eq!(
+			RateReview::weighted_pro_rating(&PRO),
+			FixedU128::saturating_from_rational(22, 5)
+		);
+
+		assert_noop!(
+			RateReview::verify_review(
+				Origin::signed(2),
+				ReviewSubject::Pro(PRO),
+				MockWorld::engagement_proof(1, PRO),
+			),
+			Error::<Test>::InvalidEngagementProof
+		);
+		assert_ok!(RateReview::verify_review(
+			Origin::signed(2),
+			ReviewSubject::Pro(PRO),
+			MockWorld::engagement_proof(2, PRO),
+		));
+		// (4 * 5 + 2 * 2) / 6
+		assert_eq!(RateReview::weighted_pro_rating(&PRO), FixedU128::saturating_from_integer(4));
+
+		// After one half life the older review counts half, the edited one in full.
+		System::set_block_number(110);
+		assert_ok!(RateReview::edit_pro_review(
+			Origin::signed(2),
+			PRO,
+			vec![1].try_into().unwrap(),
+			text(b"edited"),
+			Content::None,
+		));
+		assert_eq!(RateReview::pro_review_edited_at(&PRO, &2), Some(110));
+		// (2 * 5 + 2 * 1) / 4
+		assert_eq!(RateReview::weighted_pro_rating(&PRO), FixedU128::saturating_from_integer(3));
+
+		assert_ok!(RateReview::hide_review(Origin::root(), ReviewSubject::Pro(PRO), 1));
+		assert_eq!(RateReview::weighted_pro_rating(&PRO), FixedU128::saturating_from_integer(1));
+	});
+}
+
+#[test]
+fn weighted_pro_rating_of_an_unreviewed_pro_is_zero() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(RateReview::weighted_pro_rating(&PRO), FixedU128::from_inner(0));
+	});
+}
+
+#[test]
+fn reviewers_are_limited_per_period() {
+	new_test_ext().execute_with(|| {
+		for n in 0..10 {
+			review_property(1, H256::repeat_byte(n), 4);
+		}
+
+		Balances::make_free_balance_be(&1, 200);
+		let create = |n: u8| {
+			RateReview::create_property_review(
+				Origin::signed(1),
+				H256::repeat_byte(n),
+				4,
+				text(b"review"),
+				Content::None,
+			)
+		};
+
+		assert_noop!(create(10), Error::<Test>::ReviewLimitReached);
+		// Removing a review does not give the slot back.
+		assert_ok!(RateReview::remove_property_review(Origin::signed(1), H256::repeat_byte(0), 0));
+		assert_noop!(create(0), Error::<Test>::ReviewLimitReached);
+
+		System::set_block_number(100);
+		assert_ok!(create(10));
+		assert_eq!(RateReview::reviewer_activity(1), (200, 1));
+	});
+}
+
+#[test]
+fn review_surges_lock_the_subject() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		let subject = ReviewSubject::Property(property_id);
+
+		System::set_block_number(1);
+		for reviewer in 1..100 {
+			review_property(reviewer, property_id, 4);
+		}
+		assert_eq!(RateReview::surge_locks(&subject), None);
+
+		// The review that reaches the threshold still goes through.
+		review_property(100, property_id, 4);
+		assert_eq!(RateReview::surge_locks(&subject), Some(101));
+		assert_eq!(RateReview::property_reviews_cnt(property_id), 100);
+
+		Balances::make_free_balance_be(&101, 100);
+		let create = || {
+			RateReview::create_property_review(
+				Origin::signed(101),
+				property_id,
+				4,
+				text(b"review"),
+				Content::None,
+			)
+		};
+		assert_noop!(create(), Error::<Test>::SubjectLocked);
+
+		assert_noop!(RateReview::lift_surge_lock(Origin::signed(1), subject.clone()), BadOrigin);
+		assert_ok!(RateReview::lift_surge_lock(Origin::root(), subject.clone()));
+		assert_noop!(
+			RateReview::lift_surge_lock(Origin::root(), subject.clone()),
+			Error::<Test>::SubjectNotLocked
+		);
+		assert_ok!(create());
+	});
+}
+
+#[test]
+fn surge_window_restarts_and_locks_run_out() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		let subject = ReviewSubject::Property(property_id);
+
+		System::set_block_number(1);
+		for reviewer in 1..100 {
+			review_property(reviewer, property_id, 4);
+		}
+
+		// The window of 10 blocks is over, so counting starts again.
+		System::set_block_number(11);
+		review_property(100, property_id, 4);
+		assert_eq!(RateReview::surge_locks(&subject), None);
+		assert_eq!(RateReview::subject_activity(&subject), (21, 1));
+
+		for reviewer in 101..200 {
+			review_property(reviewer, property_id, 4);
+		}
+		assert_eq!(RateReview::surge_locks(&subject), Some(111));
+
+		System::set_block_number(111);
+		review_property(200, property_id, 4);
+		assert_eq!(RateReview::surge_locks(&subject), None);
+	});
+}
+
+#[test]
+fn verify_review_accepts_one_valid_proof_per_review() {
+	new_test_ext().execute_with(|| {
+		let subject = ReviewSubject::Pro(PRO);
+		let verify = |reviewer: u64, proof: u64| {
+			RateReview::verify_review(Origin::signed(reviewer), subject.clone(), proof)
+		};
+
+		assert_noop!(verify(1, MockWorld::engagement_proof(1, PRO)), Error::<Test>::ReviewNotFound);
+
+		review_pro(1, vec![4]);
+		assert_noop!(
+			verify(1, MockWorld::engagement_proof(2, PRO)),
+			Error::<Test>::InvalidEngagementProof
+		);
+		assert_ok!(verify(1, MockWorld::engagement_proof(1, PRO)));
+		assert!(RateReview::is_verified(&subject, &1));
+		assert_noop!(
+			verify(1, MockWorld::engagement_proof(1, PRO)),
+			Error::<Test>::ReviewAlreadyVerified
+		);
+
+		// A new review has to be verified again.
+		assert_ok!(RateReview::remove_pro_review(Origin::signed(1), PRO, 0));
+		assert!(!RateReview::is_verified(&subject, &1));
+	});
+}
+
+#[test]
+fn property_reviews_cannot_be_verified() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		review_property(1, property_id, 4);
+
+		assert_noop!(
+			RateReview::verify_review(
+				Origin::signed(1),
+				ReviewSubject::Property(property_id),
+				MockWorld::engagement_proof(1, 0),
+			),
+			Error::<Test>::InvalidEngagementProof
+		);
+	});
+}
+
+#[test]
+fn first_reward_period_ends_a_full_period_after_genesis_or_the_upgrade() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(RateReview::next_reward_at(), 1_000);
+		assert_eq!(RateReview::on_initialize(1), 0);
+		assert_eq!(RateReview::reward_period(), 0);
+
+		NextRewardAt::<Test>::kill();
+		System::set_block_number(50);
+		migrations::seed_reward_period::<Test>();
+		assert_eq!(RateReview::next_reward_at(), 1_050);
+
+		// Seeded once, later upgrades leave the running period alone.
+		System::set_block_number(60);
+		migrations::seed_reward_period::<Test>();
+		assert_eq!(RateReview::next_reward_at(), 1_050);
+	});
+}
+
+#[test]
+fn reward_pool_is_shared_by_the_likes_of_well_liked_reviews() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		let pool = RateReview::reward_pool_account();
+		Balances::make_free_balance_be(&pool, 1_001);
+
+		review_property(1, property_id, 4);
+		review_property(2, property_id, 5);
+		review_property(3, property_id, 3);
+		for liker in 100..110 {
+			assert_ok!(RateReview::like_property_review(Origin::signed(liker), property_id, 1));
+			assert_ok!(RateReview::like_property_review(Origin::signed(liker), property_id, 3));
+		}
+		for liker in 100..115 {
+			assert_ok!(RateReview::like_property_review(Origin::signed(liker), property_id, 2));
+		}
+		// Below the threshold again, so it shares nothing.
+		assert_ok!(RateReview::unlike_property_review(Origin::signed(100), property_id, 3));
+		assert_eq!(RateReview::period_rewarded_likes(0), 25);
+
+		RateReview::on_initialize(999);
+		assert_eq!(RateReview::pending_payout(), None);
+
+		RateReview::on_initialize(1_000);
+		assert_eq!(RateReview::pending_payout(), Some((0, 1_000, 25)));
+		assert_eq!(RateReview::reward_period(), 1);
+		assert_eq!(RateReview::next_reward_at(), 2_000);
+
+		// Likes from now on count towards the next period.
+		assert_ok!(RateReview::like_property_review(Origin::signed(120), property_id, 1));
+		assert_eq!(RateReview::period_likes(1, (ReviewSubject::Property(property_id), 1)), 1);
+
+		RateReview::on_initialize(1_001);
+		assert_eq!(RateReview::pending_payout(), None);
+		assert_eq!(Balances::free_balance(1), 90 + 400);
+		assert_eq!(Balances::free_balance(2), 90 + 600);
+		assert_eq!(Balances::free_balance(3), 90);
+		assert_eq!(Balances::free_balance(pool), 1);
+	});
+}
+
+#[test]
+fn tips_go_to_pros_and_review_authors() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		let subject = ReviewSubject::Property(property_id);
+		Balances::make_free_balance_be(&5, 100);
+		Balances::make_free_balance_be(&PRO, 100);
+		let tip = |who: u64, target: TipTarget<u64, H256>, amount: u64| {
+			RateReview::tip(Origin::signed(who), target, amount, Some(memo(b"thanks")))
+		};
+
+		assert_noop!(tip(5, TipTarget::Pro(PRO), 30), Error::<Test>::NotAPro);
+		MockWorld::set_profession(PRO, 0);
+		assert_noop!(tip(5, TipTarget::Pro(PRO), 0), Error::<Test>::ZeroTip);
+		assert_noop!(tip(PRO, TipTarget::Pro(PRO), 30), Error::<Test>::CannotTipSelf);
+
+		assert_ok!(tip(5, TipTarget::Pro(PRO), 30));
+		assert_eq!(Balances::free_balance(PRO), 130);
+		assert_eq!(RateReview::tips_received(PRO), 30);
+
+		review_property(1, property_id, 4);
+		assert_noop!(
+			tip(5, TipTarget::Review(subject.clone(), 2), 20),
+			Error::<Test>::ReviewNotFound
+		);
+		assert_noop!(
+			tip(1, TipTarget::Review(subject.clone(), 1), 20),
+			Error::<Test>::CannotTipSelf
+		);
+
+		assert_ok!(tip(5, TipTarget::Review(subject.clone(), 1), 20));
+		assert_eq!(Balances::free_balance(1), 90 + 20);
+		assert_eq!(RateReview::tips_received(1), 20);
+		assert_eq!(RateReview::review_tips((&subject, 1)), 20);
+
+		// Tips never reap the tipper.
+		assert!(tip(5, TipTarget::Pro(PRO), 50).is_err());
+
+		assert_ok!(RateReview::hide_review(Origin::root(), subject.clone(), 1));
+		assert_noop!(tip(5, TipTarget::Review(subject, 1), 20), Error::<Test>::ReviewIsHidden);
+	});
+}
+
+#[test]
+fn property_reviews_are_listed_by_newest_helpful_and_rating() {
+	new_test_ext().execute_with(|| {
+		let property_id = H256::repeat_byte(1);
+		MockWorld::set_property_owner(property_id, 9);
+		for (block, reviewer, rate) in [(1, 1, 2), (2, 2, 5), (3, 3, 4)] {
+			System::set_block_number(block);
+			review_property(reviewer, property_id, rate);
+		}
+		for liker in 4..6 {
+			assert_ok!(RateReview::like_property_review(Origin::signed(liker), property_id, 3));
+		}
+		assert_ok!(RateReview::like_property_review(Origin::signed(4), property_id, 1));
+		assert_ok!(RateReview::reply_to_property_review(
+			Origin::signed(9),
+			property_id,
+			3,
+			text(b"thanks")
+		));
+
+		let reviewers = |sort: ReviewSort, offset: u64, limit: u16| -> Vec<u64> {
+			RateReview::get_property_reviews(property_id, sort, false, offset, limit)
+				.into_iter()
+				.map(|x| x.reviewer)
+				.collect()
+		};
+
+		assert_eq!(reviewers(ReviewSort::Newest, 0, 10), vec![3, 2, 1]);
+		assert_eq!(reviewers(ReviewSort::Helpful, 0, 10), vec![3, 1, 2]);
+		assert_eq!(reviewers(ReviewSort::Rating, 0, 10), vec![2, 3, 1]);
+		assert_eq!(reviewers(ReviewSort::Newest, 1, 1), vec![2]);
+		assert!(RateReview::get_property_reviews(property_id, ReviewSort::Newest, true, 0, 10)
+			.is_empty());
+
+		let view =
+			&RateReview::get_property_reviews(property_id, ReviewSort::Newest, false, 0, 1)[0];
+		assert_eq!((view.rate, view.created, view.likes), (4, 3, 2));
+		assert_eq!(view.reply, Some((9, b"thanks".to_vec())));
+		assert_eq!(view.edited, None);
+
+		let summary = RateReview::get_property_rating(property_id);
+		assert_eq!(summary.average, FixedU128::saturating_from_rational(11, 3));
+		assert_eq!(summary.count, 3);
+		assert_eq!(summary.histogram, vec![0, 0, 1, 0, 1, 1]);
+		assert_eq!(summary.weighted_average, None);
+
+		assert_ok!(RateReview::hide_review(
+			Origin::root(),
+			ReviewSubject::Property(property_id),
+			2
+		));
+		assert_eq!(reviewers(ReviewSort::Rating, 0, 10), vec![3, 1]);
+	});
+}
+
+#[test]
+fn pro_rating_summary_includes_the_weighted_average_and_dimensions() {
+	new_test_ext().execute_with(|| {
+		MockWorld::set_profession(PRO, 1);
+		assert_ok!(set_dimensions(1, &[(b"quality", 1), (b"price", 1)]));
+		assert_ok!(create_pro_review(1, vec![5, 3]));
+
+		let summary = RateReview::get_pro_rating(PRO);
+		assert_eq!(summary.count, 1);
+		assert_eq!(summary.weighted_average, Some(FixedU128::saturating_from_integer(4)));
+
+		let dimensions = RateReview::get_pro_dimension_ratings(PRO);
+		assert_eq!(dimensions.len(), 2);
+		assert_eq!(dimensions[0].0, b"quality".to_vec());
+		assert_eq!(dimensions[0].1.average, FixedU128::saturating_from_integer(5));
+		assert_eq!(dimensions[1].1.average, FixedU128::saturating_from_integer(3));
+
+		let views = RateReview::get_pro_reviews(PRO, ReviewSort::Newest, false, 0, 10);
+		assert_eq!(views[0].scores, vec![5, 3]);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn review_bodies_can_live_off_chain() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		let property_id = H256::repeat_byte(1);
+		let cid = Content::IPFS(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec());
+		Balances::make_free_balance_be(&1, 100);
+
+		assert_noop!(
+			RateReview::create_property_review(
+				Origin::signed(1),
+				property_id,
+				4,
+				text(b"summary"),
+				Content::IPFS(b"cid".to_vec()),
+			),
+			Error::<Test>::InvalidContent
+		);
+		assert_ok!(RateReview::create_property_review(
+			Origin::signed(1),
+			property_id,
+			4,
+			text(b"summary"),
+			cid.clone(),
+		));
+		assert_eq!(RateReview::property_review_content((property_id, 1)), Some(cid.clone()));
+
+		assert_noop!(
+			RateReview::edit_property_review(
+				Origin::signed(1),
+				property_id,
+				4,
+				text(b"summary"),
+				Content::IPFS(b"cid".to_vec()),
+			),
+			Error::<Test>::InvalidContent
+		);
+		assert_ok!(RateReview::edit_property_review(
+			Origin::signed(1),
+			property_id,
+			4,
+			text(b"summary"),
+			Content::None,
+		));
+		assert_eq!(RateReview::property_review_content((property_id, 1)), None);
+
+		MockWorld::set_profession(PRO, 0);
+		MockWorld::connect(1, PRO);
+		assert_ok!(RateReview::create_pro_review(
+			Origin::signed(1),
+			PRO,
+			vec![4].try_into().unwrap(),
+			text(b"summary"),
+			cid.clone(),
+		));
+		let view = &RateReview::get_pro_reviews(PRO, ReviewSort::Newest, false, 0, 1)[0];
+		assert_eq!((view.review.clone(), view.content.clone()), (b"summary".to_vec(), cid));
+
+		assert_ok!(RateReview::remove_pro_review(Origin::signed(1), PRO, 0));
+		assert_eq!(RateReview::pro_review_content((PRO, 1)), None);
 	});
 }
@@ -1,5 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::prelude::{vec, vec::Vec};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
@@ -55,6 +55,15 @@ impl Content {
 	}
 }
 
+/// A portfolio entry attached to a profile.
+#[derive(Encode, Decode, Default, Clone, PartialEq, sp_core::RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Media {
+	pub content: Content,
+	pub caption: Vec<u8>,
+	pub cover: bool,
+}
+
 // #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 // pub struct WhoAndWhen<AccountId, BlockNumber, Moment> {
 // 	pub account: AccountId,
@@ -116,11 +125,12 @@ impl<Hash> Profile<Hash> {
 		};
 
 		let business = Business {
-			profession: Self::check_value(
-				Some(old.business.profession),
-				Some(new.business.profession),
-			)
-			.unwrap(),
+			// `Profession::None` is the default, so it stands for "unchanged" like `None` does.
+			profession: if new.business.profession == Profession::None {
+				old.business.profession
+			} else {
+				new.business.profession
+			},
 			field: Self::check_value(old.business.field, new.business.field),
 			company: Self::check_value(old.business.company, new.business.company),
 			website: Self::check_value(old.business.website, new.business.website),
@@ -142,6 +152,55 @@ impl<Hash> Profile<Hash> {
 		}
 	}
 
+	/// Number of fields counted by `filled_fields`.
+	pub const FIELDS: u32 = 20;
+
+	/// Number of set fields across the profile, `About` and `Business`.
+	pub fn filled_fields(&self) -> u32 {
+		let about = &self.about;
+		let business = &self.business;
+
+		[
+			self.name.is_some(),
+			self.email.is_some(),
+			self.username.is_some(),
+			self.photo.is_some(),
+			self.dob.is_some(),
+			self.bio.is_some(),
+			self.gender.as_ref().map_or(false, |x| x != &Gender::None),
+			self.profession.is_some(),
+			about.biography.is_some(),
+			about.service_area.is_some(),
+			about.education.is_some(),
+			about.awards.is_some(),
+			about.specialties.is_some(),
+			about.languages.is_some(),
+			business.profession != Profession::None,
+			business.field.as_ref().map_or(false, |x| x != &Field::None),
+			business.company.is_some(),
+			business.website.is_some(),
+			business.phone.is_some(),
+			business.email.is_some(),
+		]
+		.iter()
+		.filter(|x| **x)
+		.count() as u32
+	}
+
+	/// Private fields that are set in plain text.
+	pub fn plain_private_fields(&self) -> Vec<PrivateField> {
+		[
+			(PrivateField::Email, self.email.is_some()),
+			(PrivateField::Dob, self.dob.is_some()),
+			(PrivateField::BusinessPhone, self.business.phone.is_some()),
+			(PrivateField::BusinessEmail, self.business.email.is_some()),
+		]
+		.iter()
+		.filter(|x| x.1)
+		.map(|x| x.0)
+		.collect()
+	}
+
 	fn check_value<K>(old: Option<K>, new: Option<K>) -> Option<K> {
 		match new {
 			Some(n) => Option::Some(n),
@@ -180,6 +239,48 @@ pub struct Business<Hash> {
 	pub email: Option<Vec<u8>>,
 }
 
+/// Contact details that can be kept on chain as ciphertext instead of plain bytes.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, sp_core::RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PrivateField {
+	/// `Profile::email`
+	Email,
+	/// `Profile::dob`
+	Dob,
+	/// `Business::phone`
+	BusinessPhone,
+	/// `Business::email`
+	BusinessEmail,
+}
+
+/// A field encrypted by its owner. The symmetric key is wrapped separately for every viewer
+/// and is only valid for the `key_version` it was granted for.
+#[derive(Encode, Decode, Default, Clone, PartialEq, sp_core::RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EncryptedField<BlockNumber> {
+	pub ciphertext: Vec<u8>,
+	pub key_version: u32,
+	pub updated: BlockNumber,
+}
+
+/// Paid listing tiers of the pro directory, from lowest to highest rank.
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, sp_core::RuntimeDebug, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SubscriptionTier {
+	Premium,
+	Featured,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, sp_core::RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Subscription<BlockNumber> {
+	pub tier: SubscriptionTier,
+	pub expires: BlockNumber,
+	pub auto_renew: bool,
+}
+
 #[derive(Encode, Decode, Clone, PartialEq, sp_core::RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Gender {
@@ -195,7 +296,9 @@ impl Default for Gender {
 	}
 }
 
-#[derive(Encode, Decode, Clone, Copy, PartialEq, sp_core::RuntimeDebug, TypeInfo)]
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, sp_core::RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Profession {
 	None,
@@ -231,7 +334,9 @@ impl Default for Profession {
 	}
 }
 
-#[derive(Encode, Decode, Clone, Copy, PartialEq, sp_core::RuntimeDebug, TypeInfo)]
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, sp_core::RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum OtherProfessions {
 	CorporateRealEstateManager,
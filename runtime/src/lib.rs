@@ -36,11 +36,11 @@ use sp_runtime::{
 	generic::Era,
 	impl_opaque_keys,
 	traits::{
-		self, AccountIdLookup, BlakeTwo256, Block as BlockT, NumberFor, OpaqueKeys,
-		SaturatedConversion, StaticLookup,
+		self, AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, NumberFor,
+		OpaqueKeys, SaturatedConversion, StaticLookup,
 	},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, Perbill, Percent, Permill,
+	ApplyExtrinsicResult, DispatchError, DispatchResult, Perbill, Percent, Permill,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -49,7 +49,7 @@ use sp_version::RuntimeVersion;
 use static_assertions::const_assert;
 
 // A few exports that help ease life for downstream crates.
-use frame_support::traits::{EitherOfDiverse, Everything};
+use frame_support::traits::{tokens::nonfungibles, EitherOfDiverse, Everything};
 use frame_support::weights::ConstantMultiplier;
 pub use frame_support::{
 	construct_runtime,
@@ -83,7 +83,9 @@ mod voter_bags;
 
 pub use pallet_faucets;
 pub use pallet_friendship;
+pub use pallet_listings;
 pub use pallet_profile;
+pub use pallet_property;
 pub use pallet_rate_review;
 pub use pallet_template;
 
@@ -124,10 +126,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -947,28 +949,346 @@ impl pallet_preimage::Config for Runtime {
 	type ByteDeposit = PreimageByteDeposit;
 }
 
+parameter_types! {
+	pub const MaxCiphertextLength: u32 = 512;
+	pub const MaxWrappedKeyLength: u32 = 128;
+	pub const MaxFieldViewers: u32 = 100;
+	pub const MaxProfileDetailsLength: u32 = 4 * 1024;
+	pub const SubscriptionPeriod: BlockNumber = 30 * DAYS;
+	pub const MaxMediaItems: u32 = 20;
+	pub const MaxCaptionLength: u32 = 200;
+}
+
 impl pallet_profile::Config for Runtime {
 	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type Currency = Balances;
+	type SubscriptionFees = Treasury;
+	type SubscriptionPeriod = SubscriptionPeriod;
+	type MaxMediaItems = MaxMediaItems;
+	type MaxCaptionLength = MaxCaptionLength;
+	type MaxCiphertextLength = MaxCiphertextLength;
+	type MaxWrappedKeyLength = MaxWrappedKeyLength;
+	type MaxFieldViewers = MaxFieldViewers;
+	type MaxProfileDetailsLength = MaxProfileDetailsLength;
+	type ResponseMetrics = FriendshipResponses;
+	type FieldViewers = Connections;
+}
+
+pub struct FriendshipResponses;
+impl pallet_profile::ResponseMetrics<AccountId, BlockNumber> for FriendshipResponses {
+	fn median_response_time(account: &AccountId) -> Option<BlockNumber> {
+		Friendship::median_response_time(account)
+	}
+
+	fn response_rate(account: &AccountId) -> Option<Percent> {
+		Friendship::response_rate(account)
+	}
+}
+
+/// Private fields can only be shared with accepted connections.
+pub struct Connections;
+impl pallet_profile::FieldViewers<AccountId> for Connections {
+	fn can_view(owner: &AccountId, viewer: &AccountId) -> bool {
+		Friendship::connected((owner, viewer)) == pallet_friendship::ConnectionStatus::Connected
+	}
 }
 
 parameter_types! {
 	pub const MaxReviewLength: u32 = 600;
 	pub const MaxReplyLength: u32 = 500;
+	pub const MaxReviewEdits: u32 = 10;
+	pub const ReviewMigrationBatchSize: u32 = 500;
+	pub const ReviewReportThreshold: u32 = 5;
+	pub const MaxReportsPerReview: u32 = 50;
+	pub const ReviewDeposit: Balance = 1 * DOLLARS;
+	pub const ReviewDepositChallengePeriod: BlockNumber = 7 * DAYS;
+	pub const MaxRatingDimensions: u32 = 5;
+	pub const MaxDimensionNameLength: u32 = 32;
+	pub const ReviewerMaturity: BlockNumber = 90 * DAYS;
+	pub const RatingHalfLife: BlockNumber = 365 * DAYS;
+	pub const ReviewPeriod: BlockNumber = 1 * DAYS;
+	pub const MaxReviewsPerPeriod: u32 = 10;
+	pub const ReviewSurgeWindow: BlockNumber = 1 * HOURS;
+	pub const ReviewSurgeThreshold: u32 = 20;
+	pub const ReviewSurgeLockPeriod: BlockNumber = 1 * DAYS;
+	pub const ReviewRewardPalletId: PalletId = PalletId(*b"rp/rwrds");
+	pub const ReviewRewardPeriod: BlockNumber = 7 * DAYS;
+	pub const ReviewRewardLikeThreshold: u32 = 10;
+	pub const MaxReviewPayoutsPerBlock: u32 = 100;
+	pub const MaxTipMemoLength: u32 = 140;
+}
+
+/// Only accounts with an accepted connection to a registered pro may review them.
+pub struct ProClients;
+impl pallet_rate_review::ReviewEligibility<AccountId> for ProClients {
+	fn can_review_pro(reviewer: &AccountId, pro_id: &AccountId) -> bool {
+		Profile::pros(pro_id).is_some()
+			&& Friendship::connected((reviewer, pro_id))
+				== pallet_friendship::ConnectionStatus::Connected
+	}
+}
+
+pub struct ProfessionOfPro;
+impl pallet_rate_review::ProProfession<AccountId, rp_profile::Profession> for ProfessionOfPro {
+	fn profession(pro_id: &AccountId) -> Option<rp_profile::Profession> {
+		Profile::pros(pro_id).map(|x| x.0)
+	}
+}
+
+pub struct ReviewerStandingOf;
+impl pallet_rate_review::ReviewerStanding<AccountId, BlockNumber> for ReviewerStandingOf {
+	fn member_since(reviewer: &AccountId) -> Option<BlockNumber> {
+		Profile::get_social_account(reviewer).and_then(|x| x.profile).map(|x| x.created)
+	}
+
+	fn reputation(reviewer: &AccountId) -> u32 {
+		Profile::get_social_account(reviewer).map(|x| x.reputation).unwrap_or_default()
+	}
+
+	fn is_connected(reviewer: &AccountId, pro_id: &AccountId) -> bool {
+		Friendship::connected((reviewer, pro_id)) == pallet_friendship::ConnectionStatus::Connected
+	}
+}
+
+/// Property reviews written before the registry existed are keyed by ids that may never be
+/// registered. They stay readable and their authors can still edit or remove them, but they take
+/// no new reviews or replies unless a property gets registered under the same id.
+pub struct PropertyOwners;
+impl pallet_rate_review::PropertyOwnership<Hash, AccountId> for PropertyOwners {
+	fn exists(property_id: &Hash) -> bool {
+		Property::properties(property_id).is_some()
+	}
+
+	fn is_owner(property_id: &Hash, who: &AccountId) -> bool {
+		Property::is_owner(property_id, who)
+	}
+}
+
+/// There is no escrow or invoicing pallet in the runtime to check against yet, so only pro
+/// attestations are accepted. Properties have nobody to attest, so their reviews cannot be
+/// verified.
+pub struct EngagementOf;
+impl
+	pallet_rate_review::VerifyEngagement<
+		AccountId,
+		Hash,
+		pallet_rate_review::EngagementAttestation<Hash, Signature>,
+	> for EngagementOf
+{
+	fn verify(
+		reviewer: &AccountId,
+		subject: &pallet_rate_review::ReviewSubject<AccountId, Hash>,
+		proof: &pallet_rate_review::EngagementAttestation<Hash, Signature>,
+	) -> bool {
+		match subject {
+			pallet_rate_review::ReviewSubject::Pro(pro_id) => traits::Verify::verify(
+				&proof.1,
+				&(b"realm3/engagement", subject, reviewer, proof.0).encode()[..],
+				pro_id,
+			),
+			pallet_rate_review::ReviewSubject::Property(_) => false,
+		}
+	}
 }
 
 impl pallet_rate_review::Config for Runtime {
 	type Event = Event;
+	type PropertyOwnership = PropertyOwners;
+	type ReviewEligibility = ProClients;
+	type EngagementProof = pallet_rate_review::EngagementAttestation<Hash, Signature>;
+	type EngagementVerifier = EngagementOf;
+	type ModerationOrigin = EnsureRootOrHalfCouncil;
+	type ReportThreshold = ReviewReportThreshold;
+	type MaxReportsPerReview = MaxReportsPerReview;
+	type Currency = Balances;
+	type ReviewDeposit = ReviewDeposit;
+	type DepositChallengePeriod = ReviewDepositChallengePeriod;
+	type Slashed = Treasury;
+	type ReviewPeriod = ReviewPeriod;
+	type MaxReviewsPerPeriod = MaxReviewsPerPeriod;
+	type SurgeWindow = ReviewSurgeWindow;
+	type SurgeThreshold = ReviewSurgeThreshold;
+	type SurgeLockPeriod = ReviewSurgeLockPeriod;
+	type RewardPalletId = ReviewRewardPalletId;
+	type RewardPeriod = ReviewRewardPeriod;
+	type RewardLikeThreshold = ReviewRewardLikeThreshold;
+	type MaxPayoutsPerBlock = MaxReviewPayoutsPerBlock;
+	type MaxTipMemoLength = MaxTipMemoLength;
+	type Profession = rp_profile::Profession;
+	type ProProfession = ProfessionOfPro;
+	type MaxRatingDimensions = MaxRatingDimensions;
+	type MaxDimensionNameLength = MaxDimensionNameLength;
+	type ReviewerStanding = ReviewerStandingOf;
+	type ReviewerMaturity = ReviewerMaturity;
+	type RatingHalfLife = RatingHalfLife;
 	type MaxReviewLength = MaxReviewLength;
 	type MaxReplyLength = MaxReplyLength;
+	type MaxReviewEdits = MaxReviewEdits;
+	type MigrationBatchSize = ReviewMigrationBatchSize;
 }
 
 parameter_types! {
 	pub const MaxConnectionRequests: u32 = 10;
+	pub const MaxResponseSamples: u32 = 20;
+	pub const ConnectionRequesterMaturity: BlockNumber = 7 * DAYS;
+}
+
+/// Only requests from profiles that have been around for a while count towards response rates,
+/// so that freshly created accounts cannot be used to drag them down.
+pub struct EstablishedRequesters;
+impl pallet_friendship::RequesterStanding<AccountId> for EstablishedRequesters {
+	fn counts(requester: &AccountId) -> bool {
+		Profile::get_social_account(requester)
+			.and_then(|x| x.profile)
+			.map_or(false, |x| {
+				System::block_number().saturating_sub(x.created)
+					>= ConnectionRequesterMaturity::get()
+			})
+	}
 }
 
 impl pallet_friendship::Config for Runtime {
 	type Event = Event;
 	type MaxConnectionRequests = MaxConnectionRequests;
+	type MaxResponseSamples = MaxResponseSamples;
+	type RequesterStanding = EstablishedRequesters;
+}
+
+parameter_types! {
+	pub const UniquesClassDeposit: Balance = 100 * DOLLARS;
+	pub const UniquesInstanceDeposit: Balance = DOLLARS;
+	pub const UniquesMetadataDepositBase: Balance = 10 * DOLLARS;
+	pub const UniquesDepositPerByte: Balance = CENTS;
+	pub const UniquesStringLimit: u32 = 128;
+	pub const UniquesKeyLimit: u32 = 32;
+	pub const UniquesValueLimit: u32 = 256;
+}
+
+impl pallet_uniques::Config for Runtime {
+	type Event = Event;
+	type ClassId = u32;
+	type InstanceId = Hash;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type ClassDeposit = UniquesClassDeposit;
+	type InstanceDeposit = UniquesInstanceDeposit;
+	type MetadataDepositBase = UniquesMetadataDepositBase;
+	type AttributeDepositBase = UniquesMetadataDepositBase;
+	type DepositPerByte = UniquesDepositPerByte;
+	type StringLimit = UniquesStringLimit;
+	type KeyLimit = UniquesKeyLimit;
+	type ValueLimit = UniquesValueLimit;
+	type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxAddressLength: u32 = 256;
+	pub const PropertyDeposit: Balance = 10 * DOLLARS;
+	pub const PropertyCollection: u32 = 0;
+	pub const PropertyPalletId: PalletId = PalletId(*b"rp/prprt");
+}
+
+/// Property tokens are `pallet_uniques` instances of `PropertyCollection`, with the property id
+/// as instance id. The collection is created by the property pallet, owned by its account and
+/// holds no deposits. Tokens are only minted into it while that account owns it.
+pub struct PropertyNfts;
+impl pallet_property::PropertyTokens<Hash, AccountId> for PropertyNfts {
+	fn create_collection() -> DispatchResult {
+		Uniques::force_create(
+			frame_system::RawOrigin::Root.into(),
+			PropertyCollection::get(),
+			<Runtime as frame_system::Config>::Lookup::unlookup(
+				PropertyPalletId::get().into_account_truncating(),
+			),
+			true,
+		)
+	}
+
+	fn holder(property_id: &Hash) -> Option<AccountId> {
+		<Uniques as nonfungibles::Inspect<AccountId>>::owner(
+			&PropertyCollection::get(),
+			property_id,
+		)
+	}
+
+	fn mint(property_id: &Hash, owner: &AccountId) -> DispatchResult {
+		let collection = PropertyCollection::get();
+		let account: AccountId = PropertyPalletId::get().into_account_truncating();
+
+		if <Uniques as nonfungibles::Inspect<AccountId>>::class_owner(&collection) != Some(account)
+		{
+			return Err(DispatchError::Other("PropertyCollectionNotOwned"));
+		}
+
+		<Uniques as nonfungibles::Mutate<AccountId>>::mint_into(&collection, property_id, owner)
+	}
+
+	fn set_metadata(
+		property_id: &Hash,
+		content: &rp_profile::Content,
+		area: rp_profile::Area,
+	) -> DispatchResult {
+		use scale_info::prelude::string::ToString;
+
+		for (key, value) in [
+			(&b"content"[..], Vec::<u8>::from(content.clone())),
+			(&b"area"[..], area.to_string().into_bytes()),
+		] {
+			Uniques::set_attribute(
+				frame_system::RawOrigin::Root.into(),
+				PropertyCollection::get(),
+				Some(*property_id),
+				key.to_vec()
+					.try_into()
+					.map_err(|_| DispatchError::Other("AttributeKeyTooLong"))?,
+				value.try_into().map_err(|_| DispatchError::Other("AttributeValueTooLong"))?,
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl pallet_property::Config for Runtime {
+	type Event = Event;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type MaxAddressLength = MaxAddressLength;
+	type PropertyTokens = PropertyNfts;
+	type Currency = Balances;
+	type PropertyDeposit = PropertyDeposit;
+	type Slashed = Treasury;
+}
+
+pub struct RegisteredProperties;
+impl pallet_listings::PropertyRegistry<Hash, AccountId> for RegisteredProperties {
+	fn is_owner(property_id: &Hash, who: &AccountId) -> bool {
+		Property::is_owner(property_id, who)
+	}
+
+	fn area(property_id: &Hash) -> Option<rp_profile::Area> {
+		Property::properties(property_id).map(|x| x.area)
+	}
+}
+
+pub struct RealEstateAgents;
+impl pallet_listings::ListingAgents<AccountId> for RealEstateAgents {
+	fn is_agent(who: &AccountId) -> bool {
+		matches!(
+			Profile::pros(who).map(|x| x.0),
+			Some(
+				rp_profile::Profession::RealEstateAgent | rp_profile::Profession::RealEstateBroker
+			)
+		)
+	}
+}
+
+impl pallet_listings::Config for Runtime {
+	type Event = Event;
+	type PropertyRegistry = RegisteredProperties;
+	type ListingAgents = RealEstateAgents;
 }
 
 impl pallet_faucets::Config for Runtime {
@@ -1018,6 +1338,9 @@ construct_runtime!(
 		Profile: pallet_profile,
 		RateReview: pallet_rate_review,
 		Friendship: pallet_friendship,
+		Property: pallet_property,
+		Uniques: pallet_uniques,
+		Listings: pallet_listings,
 		Faucets: pallet_faucets,
 	}
 );
@@ -1254,7 +1577,7 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl profile_runtime_api::ProfileApi<Block, AccountId, Hash> for Runtime {
+	impl profile_runtime_api::ProfileApi<Block, AccountId, Hash, BlockNumber> for Runtime {
 		fn is_username_exist(username: Hash) -> bool {
 			Profile::is_username_exist(username)
 		}
@@ -1264,8 +1587,83 @@ impl_runtime_apis! {
 			area: rp_profile::Area,
 			offset: u64,
 			limit: u16,
-		) -> Vec<pallet_profile::rpc::ProProfile<AccountId>> {
-			Profile::get_pros(profession, area, offset, limit)
+			include_unavailable: bool,
+		) -> Vec<pallet_profile::rpc::ProProfile<AccountId, BlockNumber>> {
+			Profile::get_pros(profession, area, offset, limit, include_unavailable)
+		}
+
+		fn get_profile_media(account_id: AccountId) -> Vec<rp_profile::Media> {
+			Profile::get_profile_media(account_id)
+		}
+
+		fn get_private_field(
+			owner: AccountId,
+			field: rp_profile::PrivateField,
+			viewer: AccountId,
+		) -> Option<(Vec<u8>, Vec<u8>)> {
+			Profile::get_private_field(owner, field, viewer)
+		}
+	}
+
+	impl rate_review_runtime_api::RateReviewApi<Block, AccountId, Hash, BlockNumber, Balance> for Runtime {
+		fn get_pro_rating(pro_id: AccountId) -> pallet_rate_review::rpc::RatingSummary {
+			RateReview::get_pro_rating(pro_id)
+		}
+
+		fn get_property_rating(property_id: Hash) -> pallet_rate_review::rpc::RatingSummary {
+			RateReview::get_property_rating(property_id)
+		}
+
+		fn get_pro_dimension_ratings(
+			pro_id: AccountId,
+		) -> Vec<(Vec<u8>, pallet_rate_review::rpc::RatingSummary)> {
+			RateReview::get_pro_dimension_ratings(pro_id)
+		}
+
+		fn get_pro_reviews(
+			pro_id: AccountId,
+			sort: pallet_rate_review::rpc::ReviewSort,
+			verified_only: bool,
+			offset: u64,
+			limit: u16,
+		) -> Vec<pallet_rate_review::rpc::ReviewView<AccountId, BlockNumber, Balance>> {
+			RateReview::get_pro_reviews(pro_id, sort, verified_only, offset, limit)
+		}
+
+		fn get_property_reviews(
+			property_id: Hash,
+			sort: pallet_rate_review::rpc::ReviewSort,
+			verified_only: bool,
+			offset: u64,
+			limit: u16,
+		) -> Vec<pallet_rate_review::rpc::ReviewView<AccountId, BlockNumber, Balance>> {
+			RateReview::get_property_reviews(property_id, sort, verified_only, offset, limit)
+		}
+
+		fn get_reviewer_reviews(
+			reviewer: AccountId,
+			offset: u64,
+			limit: u16,
+		) -> Vec<(
+			pallet_rate_review::ReviewSubject<AccountId, Hash>,
+			pallet_rate_review::rpc::ReviewView<AccountId, BlockNumber, Balance>,
+		)> {
+			RateReview::get_reviewer_reviews(reviewer, offset, limit)
+		}
+
+		fn get_tips_received(account: AccountId) -> Balance {
+			RateReview::get_tips_received(account)
+		}
+	}
+
+	impl listings_runtime_api::ListingsApi<Block, AccountId, Hash, BlockNumber> for Runtime {
+		fn get_listings(
+			area: rp_profile::Area,
+			status: Option<pallet_listings::ListingStatus>,
+			offset: u64,
+			limit: u16,
+		) -> Vec<pallet_listings::rpc::ListingView<AccountId, Hash, BlockNumber>> {
+			Listings::get_listings(area, status, offset, limit)
 		}
 	}
 